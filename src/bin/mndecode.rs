@@ -1,8 +1,17 @@
-use std::io::Read;
+use std::io::{self, Write};
+use std::process;
 
-fn main() -> mnemonic::Result<()> {
-    let mut input = vec![];
-    std::io::stdin().read_to_end(&mut input)?;
-    mnemonic::decode(input, std::io::stdout())?;
+fn run() -> mnemonic::Result<()> {
+    let mut reader = mnemonic::MnemonicReader::new(io::stdin().lock());
+    let mut stdout = io::stdout().lock();
+    io::copy(&mut reader, &mut stdout)?;
+    stdout.flush()?;
     Ok(())
 }
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("mndecode: {}", e);
+        process::exit(1);
+    }
+}
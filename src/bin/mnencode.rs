@@ -1,8 +1,8 @@
-use std::io::Read;
+use std::io::{self, Write};
 
 fn main() -> mnemonic::Result<()> {
-    let mut input = vec![];
-    std::io::stdin().read_to_end(&mut input)?;
-    mnemonic::encode(input, std::io::stdout())?;
+    let mut writer = mnemonic::MnemonicWriter::new(io::stdout().lock());
+    io::copy(&mut io::stdin().lock(), &mut writer)?;
+    writer.finish()?.flush()?;
     Ok(())
 }
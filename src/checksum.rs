@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+use std::io::{self, Write};
+
+use crate::{decode_to_vec, mn_encode_with, mn_resolve_exact, mn_words, Format, Position, Result,
+            MN_BASE, MN_WORDS};
+use crate::Error::*;
+
+/// Encode the bytes of `src` with a custom template, followed by `check_words` words derived
+/// from a checksum of `src`.
+///
+/// The check words are the base-1626 digits of the CRC-32 of `src`, least significant first,
+/// and are formatted like the other words.  One check word detects all but 1 in 1626 random
+/// errors; three words use the full 32-bit checksum.  Decode the result with
+/// [`decode_with_checksum`], using the same number of check words.
+///
+/// ## Panics
+///
+/// Panics if `check_words` is not between 1 and 3.
+///
+/// ## Example
+///
+/// ```
+/// let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+/// let mut dest = Vec::<u8>::new();
+///
+/// mnemonic::encode_with_checksum(&bytes, mnemonic::MN_FDEFAULT, 1, &mut dest).unwrap();
+/// assert_eq!(dest, &b"digital-apollo-aroma--rival-artist-rebel--chris"[..]);
+/// ```
+pub fn encode_with_checksum<S, F, W>(src: S, format: F, check_words: usize, mut dest: W)
+    -> io::Result<()>
+    where S: AsRef<[u8]>,
+          F: AsRef<[u8]>,
+          W: Write
+{
+    assert!((1..=3).contains(&check_words), "check_words must be between 1 and 3");
+    let (src, format) = (src.as_ref(), Format::from_bytes(format.as_ref())?);
+    mn_encode_with(src, &format, MN_BASE, mn_check_word_indices(src, check_words),
+                   |index| MN_WORDS[index as usize],
+                   |s| dest.write_all(s))
+}
+
+/// Decode a mnemonic string produced by [`encode_with_checksum`], and write the bytes to `dest`
+/// if the last `check_words` words match their checksum.
+///
+/// Nothing is written to `dest` unless the checksum matches.  A mismatch is reported as
+/// [`Error::ChecksumMismatch`](crate::Error::ChecksumMismatch).
+///
+/// ## Panics
+///
+/// Panics if `check_words` is not between 1 and 3.
+///
+/// ## Example
+///
+/// ```
+/// let mut dest = Vec::<u8>::new();
+///
+/// let src = "digital-apollo-aroma--rival-artist-rebel--chris";
+/// mnemonic::decode_with_checksum(src, 1, &mut dest).unwrap();
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+///
+/// let swapped = "digital-apollo-aroma--rival-rebel-artist--lotus";
+/// assert!(mnemonic::decode_with_checksum(swapped, 1, &mut dest).is_err());
+/// ```
+pub fn decode_with_checksum<S, W>(src: S, check_words: usize, mut dest: W) -> Result<usize>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    assert!((1..=3).contains(&check_words), "check_words must be between 1 and 3");
+    let src = src.as_ref();
+    let words: Vec<_> = mn_words(src).collect();
+    if words.len() < check_words {
+        return Err(ChecksumMismatch)
+    }
+    let split = words.len() - check_words;
+    let decoded = decode_to_vec(&src[..words[split].0])?;
+    let expected = mn_check_word_indices(&decoded, check_words);
+    for (n, (&(start, word), expected)) in words[split..].iter().zip(expected).enumerate() {
+        let position = Position { word: split + n, bytes: Some(start..start + word.len()) };
+        if mn_resolve_exact(word, &position)? != expected {
+            return Err(ChecksumMismatch)
+        }
+    }
+    dest.write_all(&decoded)?;
+    Ok(decoded.len())
+}
+
+/// The indices in the MN_WORDS array of the check words for `src`.
+fn mn_check_word_indices(src: &[u8], check_words: usize) -> impl Iterator<Item = u32> {
+    let crc = mn_crc32(src);
+    (0..check_words as u32).map(move |k| crc / MN_BASE.pow(k) % MN_BASE)
+}
+
+/// The CRC-32 (IEEE 802.3) checksum of `data`.
+fn mn_crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+    use crate::{to_string, Error};
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(mn_crc32(b""), 0);
+        assert_eq!(mn_crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_checksum_errors() {
+        let mut dest = Vec::new();
+        let mut encoded = Vec::new();
+        encode_with_checksum([0x01, 0xE2, 0x40], "x-x-x--", 1, &mut encoded).unwrap();
+        assert_eq!(encoded, b"consul-quiet-fax--zipper");
+
+        let substituted = "academy-quiet-fax--zipper";
+        assert!(matches!(decode_with_checksum(substituted, 1, &mut dest), Err(Error::ChecksumMismatch)));
+        assert!(matches!(decode_with_checksum("zipper", 2, &mut dest), Err(Error::ChecksumMismatch)));
+        assert!(matches!(decode_with_checksum("consul-quiet-fax-aplolo", 1, &mut dest),
+                         Err(Error::UnrecognizedWord { .. })));
+        assert!(dest.is_empty());
+    }
+
+    quickcheck! {
+        fn quickcheck_checksum_round_trip(src: Vec<u8>) -> bool {
+            let mut encoded = Vec::new();
+            encode_with_checksum(&src, "x-x-x--", 2, &mut encoded).unwrap();
+            let mut decoded = Vec::new();
+            decode_with_checksum(&encoded, 2, &mut decoded).unwrap();
+            decoded == src && encoded.starts_with(to_string(&src).as_bytes())
+        }
+    }
+}
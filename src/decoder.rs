@@ -0,0 +1,359 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::error::WordError;
+use crate::fuzzy::mn_resolve_fuzzy;
+use crate::normalize::Normalized;
+use crate::prefix::mn_resolve_prefix;
+use crate::wordlist::mn_check_wordlist;
+use crate::{mn_decode_indices, mn_words};
+use crate::{Correction, DefaultWordlist, Error, Format, Position, Result, Wordlist};
+
+/// A decoder with configurable options.
+///
+/// By default, a `Decoder` behaves like [`decode`](crate::decode): it requires each word to be
+/// an exact lowercase match for a word in the list, and accepts any non-letters between words.
+/// Its methods change these settings, and can be chained:
+///
+/// ```
+/// use mnemonic::{Decoder, Format};
+///
+/// let decoder = Decoder::new()
+///     .normalize(true)
+///     .prefixes(true)
+///     .max_distance(1)
+///     .format(Format::new("x x x\n").unwrap())
+///     .max_len(64);
+///
+/// let decoded = decoder.decode_to_vec("Digi aplolo arom\nrival artist rebel").unwrap();
+/// assert_eq!(decoded, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Decoder<L = DefaultWordlist> {
+    wordlist: L,
+    normalize: bool,
+    prefixes: bool,
+    max_distance: usize,
+    format: Option<Format>,
+    max_len: Option<usize>,
+    allow_trailing: bool,
+}
+
+impl Decoder {
+    /// A decoder with the default settings.
+    pub fn new() -> Self {
+        Decoder::default()
+    }
+}
+
+impl<L: Wordlist> Decoder<L> {
+    /// Look words up in `wordlist` instead of the standard word list.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+    pub fn wordlist<M: Wordlist>(self, wordlist: M) -> Decoder<M> {
+        mn_check_wordlist(&wordlist);
+        Decoder {
+            wordlist,
+            normalize: self.normalize,
+            prefixes: self.prefixes,
+            max_distance: self.max_distance,
+            format: self.format,
+            max_len: self.max_len,
+            allow_trailing: self.allow_trailing,
+        }
+    }
+
+    /// Ignore case and invisible characters, as in
+    /// [`decode_normalized`](crate::decode_normalized).
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Accept any unambiguous prefix of a word, as in [`decode_prefix`](crate::decode_prefix).
+    pub fn prefixes(mut self, prefixes: bool) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Correct words that are not in the list to the closest word at most `max_distance` edits
+    /// away, as in [`decode_fuzzy`](crate::decode_fuzzy).  The default of 0 disables correction.
+    ///
+    /// If prefixes are also accepted, a word is corrected only if it is not a prefix of any word.
+    /// Use [`decode_with_corrections`](Decoder::decode_with_corrections) to find out which words
+    /// were corrected.
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Require the input to be in the given format, as in [`Format::decode`].
+    ///
+    /// If the input is also normalized, the case of each word is not checked.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Fail with [`Error::TooLong`] if the decoded data would be longer than `max_len` bytes.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Stop decoding at the first word that is not recognized, and ignore it and the rest of
+    /// the input, instead of failing.
+    pub fn allow_trailing(mut self, allow_trailing: bool) -> Self {
+        self.allow_trailing = allow_trailing;
+        self
+    }
+
+    /// Decode the mnemonic string `src` into bytes, and write the bytes to `dest`.
+    #[cfg(feature = "std")]
+    pub fn decode<S, W>(&self, src: S, mut dest: W) -> Result<usize>
+        where S: AsRef<[u8]>,
+              W: Write
+    {
+        self.mn_decode(src.as_ref(), &mut Vec::new(), |bytes| Ok(dest.write_all(bytes)?))
+    }
+
+    /// Decode the mnemonic string `src` and return the bytes as a `Vec`.
+    ///
+    /// Unlike [`decode`](Decoder::decode), this is available without the `std` feature.
+    pub fn decode_to_vec<S: AsRef<[u8]>>(&self, src: S) -> Result<Vec<u8>> {
+        let mut dest = Vec::new();
+        self.mn_decode(src.as_ref(), &mut Vec::new(), |bytes| {
+            dest.extend_from_slice(bytes);
+            Ok(())
+        })?;
+        Ok(dest)
+    }
+
+    /// Decode the mnemonic string `src`, and return the bytes along with the corrections made
+    /// to misspelled words.
+    ///
+    /// Corrections are only made if [`max_distance`](Decoder::max_distance) is set.  As in
+    /// errors, their positions refer to the bytes of `src`, but their words are normalized if
+    /// [`normalize`](Decoder::normalize) is set.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let decoder = mnemonic::Decoder::new().max_distance(1);
+    /// let (decoded, corrections) = decoder.decode_with_corrections("consul-quite-fax").unwrap();
+    ///
+    /// assert_eq!(decoded, [0x01, 0xE2, 0x40]);
+    /// assert_eq!(corrections[0].to_string(), "corrected 'quite' to 'quiet' at word 2 (bytes 7..12)");
+    /// ```
+    pub fn decode_with_corrections<S: AsRef<[u8]>>(&self, src: S)
+        -> Result<(Vec<u8>, Vec<Correction>)>
+    {
+        let mut dest = Vec::new();
+        let mut corrections = Vec::new();
+        self.mn_decode(src.as_ref(), &mut corrections, |bytes| {
+            dest.extend_from_slice(bytes);
+            Ok(())
+        })?;
+        Ok((dest, corrections))
+    }
+
+    /// Decode the mnemonic string `src` into `dest`, and return the number of bytes decoded.
+    ///
+    /// Fails with [`Error::BufferTooSmall`] if the decoded data does not fit in `dest`.
+    pub fn decode_to_slice<S: AsRef<[u8]>>(&self, src: S, dest: &mut [u8]) -> Result<usize> {
+        let capacity = dest.len();
+        let mut len = 0;
+        self.mn_decode(src.as_ref(), &mut Vec::new(), |bytes| {
+            let end = len + bytes.len();
+            dest.get_mut(len..end)
+                .ok_or(Error::BufferTooSmall { capacity })?
+                .copy_from_slice(bytes);
+            len = end;
+            Ok(())
+        })
+    }
+
+    /// Decode the mnemonic string `src` into an array of exactly `N` bytes.
+    ///
+    /// Fails with [`Error::BufferTooSmall`] if the decoded data is longer than `N` bytes, or
+    /// [`Error::WrongLength`] if it is shorter.
+    pub fn decode_to_array<const N: usize>(&self, src: impl AsRef<[u8]>) -> Result<[u8; N]> {
+        let mut dest = [0; N];
+        let len = self.decode_to_slice(src, &mut dest)?;
+        if len != N {
+            return Err(Error::WrongLength { expected: N, len })
+        }
+        Ok(dest)
+    }
+
+    /// Decode `src`, passing each decoded chunk of bytes to `emit` and appending any
+    /// corrections to `corrections`.
+    fn mn_decode<F>(&self, src: &[u8], corrections: &mut Vec<Correction>, mut emit: F)
+        -> Result<usize>
+        where F: FnMut(&[u8]) -> Result<()>
+    {
+        let mut len = 0;
+        let emit = |bytes: &[u8]| {
+            len += bytes.len();
+            match self.max_len {
+                Some(max_len) if len > max_len => Err(Error::TooLong { max_len }),
+                _ => emit(bytes),
+            }
+        };
+        if self.normalize {
+            let normalized = Normalized::new(src);
+            let start = corrections.len();
+            let result = self.mn_decode_words(&normalized.text, corrections, emit);
+            for correction in &mut corrections[start..] {
+                normalized.map_position(&mut correction.position);
+            }
+            result.map_err(|e| normalized.map_error(e))
+        } else {
+            self.mn_decode_words(src, corrections, emit)
+        }
+    }
+
+    /// Split `src` into words, checking its format if there is one, and decode them.
+    fn mn_decode_words<F>(&self, src: &[u8], corrections: &mut Vec<Correction>, emit: F)
+        -> Result<usize>
+        where F: FnMut(&[u8]) -> Result<()>
+    {
+        let mut resolve = |word: &[u8], position: &Position| {
+            self.resolve(word, position, corrections)
+        };
+        let base = self.wordlist.base();
+        if let Some(format) = &self.format {
+            return format.mn_decode_with(src, base, !self.normalize, self.allow_trailing, resolve,
+                                         emit)
+        }
+        let indices = mn_words(src).enumerate().map_while(|(n, (start, word))| {
+            let position = Position { word: n, bytes: Some(start..start + word.len()) };
+            match resolve(word, &position) {
+                Err(Error::UnrecognizedWord { .. }) if self.allow_trailing => None,
+                i => Some(i.map(|i| (i, word, position))),
+            }
+        });
+        mn_decode_indices(indices, base, emit)
+    }
+
+    /// Find the index of `word`, appending any correction to `corrections`.
+    fn resolve(&self, word: &[u8], position: &Position, corrections: &mut Vec<Correction>)
+        -> Result<u32>
+    {
+        let result = match self.prefixes {
+            true => mn_resolve_prefix(&self.wordlist, word, position),
+            false => self.wordlist.index(word)
+                                  .ok_or_else(|| WordError::UnrecognizedWord.at(word, position.clone())),
+        };
+        match result {
+            Err(Error::UnrecognizedWord { .. }) if self.max_distance > 0 => {
+                mn_resolve_fuzzy(&self.wordlist, word, position, self.max_distance, corrections)
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let src = "digital-apollo-aroma--rival-artist-rebel";
+        assert_eq!(Decoder::new().decode_to_vec(src).unwrap(), crate::decode_to_vec(src).unwrap());
+        let e = Decoder::new().decode_to_vec("Digital").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'Digital' at word 1 (bytes 0..7)");
+    }
+
+    #[test]
+    fn test_options() {
+        let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+        let decoder = Decoder::new().prefixes(true).max_distance(1);
+        assert_eq!(decoder.decode_to_vec("digi aplolo arom rival artist rebel").unwrap(), bytes);
+        let e = Decoder::new().max_distance(1).decode_to_vec("digi").unwrap_err();
+        assert!(matches!(e, Error::UnrecognizedWord { .. }));
+
+        let decoder = Decoder::new().normalize(true).format(Format::default());
+        assert_eq!(decoder.decode_to_vec("DIGITAL-Apollo-aroma").unwrap(), &bytes[..4]);
+        let e = decoder.decode_to_vec("DIGITAL Apollo").unwrap_err();
+        assert_eq!(e.to_string(), "unexpected separator ' ' before word 2 (bytes 7..8): expected '-'");
+
+        let decoder = Decoder::new().max_len(4);
+        assert_eq!(decoder.decode_to_vec("digital-apollo-aroma").unwrap(), &bytes[..4]);
+        let e = decoder.decode_to_vec("digital-apollo-aroma--rival").unwrap_err();
+        assert_eq!(e.to_string(), "decoded data is longer than 4 bytes");
+    }
+
+    #[test]
+    fn test_decode_to_slice() {
+        let src = "digital-apollo-aroma--rival-artist-rebel";
+        let mut buf = [0; 10];
+        assert_eq!(Decoder::new().decode_to_slice(src, &mut buf).unwrap(), 8);
+        assert_eq!(buf, [101, 2, 240, 6, 108, 11, 20, 97, 0, 0]);
+
+        let e = Decoder::new().decode_to_slice(src, &mut buf[..7]).unwrap_err();
+        assert_eq!(e.to_string(), "decoded data does not fit in a buffer of 7 bytes");
+        let e = Decoder::new().decode_to_slice(src, &mut buf[..4]).unwrap_err();
+        assert!(matches!(e, Error::BufferTooSmall { capacity: 4 }));
+        assert_eq!(Decoder::new().decode_to_slice("", &mut []).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_decode_to_array() {
+        let decoder = Decoder::new();
+        assert_eq!(decoder.decode_to_array::<4>("digital-apollo-aroma").unwrap(), [101, 2, 240, 6]);
+        let e = decoder.decode_to_array::<3>("digital-apollo-aroma").unwrap_err();
+        assert!(matches!(e, Error::BufferTooSmall { capacity: 3 }));
+        let e = decoder.decode_to_array::<5>("digital-apollo-aroma").unwrap_err();
+        assert_eq!(e.to_string(), "expected 5 bytes of decoded data, found 4");
+    }
+
+    #[test]
+    fn test_wordlist() {
+        let bytes = [0x01, 0xE2, 0x40];
+        let decoder = Decoder::new().wordlist(crate::wordlist::tests::Reversed);
+        assert_eq!(decoder.decode_to_vec("needle-russian-fax").unwrap(), bytes);
+
+        let decoder = decoder.prefixes(true).max_distance(1).format(Format::default());
+        assert_eq!(decoder.decode_to_vec("need-rusian-fax").unwrap(), bytes);
+        let e = decoder.decode_to_vec("ne-russian-fax").unwrap_err();
+        assert!(matches!(e, Error::AmbiguousWord { .. }));
+        let e = decoder.decode_to_vec("need russian fax").unwrap_err();
+        assert!(matches!(e, Error::UnexpectedSeparator { .. }));
+    }
+
+    #[test]
+    fn test_decode_with_corrections() {
+        let decoder = Decoder::new().normalize(true).prefixes(true).max_distance(1);
+        let (decoded, corrections) = decoder.decode_with_corrections("Digi APLOLO aroma").unwrap();
+        assert_eq!(decoded, [101, 2, 240, 6]);
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].original, "aplolo");
+        assert_eq!(corrections[0].corrected, "apollo");
+        assert_eq!(corrections[0].position, Position { word: 1, bytes: Some(5..11) });
+
+        let src = "digi\u{200B}tal aplolo aroma";
+        let (_, corrections) = decoder.decode_with_corrections(src).unwrap();
+        assert_eq!(corrections[0].position.bytes, Some(11..17));
+
+        let (_, corrections) = Decoder::new().decode_with_corrections("digital").unwrap();
+        assert!(corrections.is_empty());
+    }
+
+    #[test]
+    fn test_allow_trailing() {
+        let bytes = [101, 2, 240, 6];
+        let decoder = Decoder::new().allow_trailing(true);
+        assert_eq!(decoder.decode_to_vec("digital apollo aroma. Thanks!").unwrap(), bytes);
+        assert_eq!(decoder.decode_to_vec("").unwrap(), []);
+
+        let decoder = decoder.format(Format::default());
+        assert_eq!(decoder.decode_to_vec("digital-apollo-aroma. Thanks!").unwrap(), bytes);
+        let e = decoder.decode_to_vec("digital-apollo aroma. Thanks!").unwrap_err();
+        assert!(matches!(e, Error::UnexpectedSeparator { .. }));
+    }
+}
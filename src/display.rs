@@ -0,0 +1,181 @@
+use core::fmt::{self, Alignment, Write};
+use core::str;
+
+use crate::{mn_encode_word, mn_words_required, Case, Format};
+
+/// Display the bytes of `src` as a mnemonic string, without allocating a `String`.
+///
+/// The result is the same as [`to_string`](crate::to_string), with the default [`Format`]
+/// unless another is given with [`Display::format`].  The alternate flag `{:#}` writes every
+/// word in uppercase, and a width pads the string like any other `Display` value.
+///
+/// ## Example
+///
+/// ```
+/// let bytes = [101, 2, 240, 6];
+/// assert_eq!(format!("{}", mnemonic::display(&bytes)), "digital-apollo-aroma");
+/// assert_eq!(format!("{:#}", mnemonic::display(&bytes)), "DIGITAL-APOLLO-AROMA");
+/// assert_eq!(format!("[{:>22}]", mnemonic::display(&bytes)), "[  digital-apollo-aroma]");
+///
+/// let format = mnemonic::Format::new("x x\n").unwrap();
+/// assert_eq!(mnemonic::display(&bytes).format(&format).to_string(), "digital apollo\naroma");
+/// ```
+pub fn display<S: AsRef<[u8]> + ?Sized>(src: &S) -> Display<'_> {
+    Display { src: src.as_ref(), format: None }
+}
+
+/// Helper struct for displaying bytes as a mnemonic string.
+///
+/// This is created by [`display`].
+#[derive(Clone, Copy, Debug)]
+pub struct Display<'a> {
+    src: &'a [u8],
+    format: Option<&'a Format>,
+}
+
+impl<'a> Display<'a> {
+    /// Display the words in the given format.
+    pub fn format(mut self, format: &'a Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Pass each piece of the string to `emit`, with every word in uppercase if `upper` is set.
+    fn write<F>(&self, upper: bool, mut emit: F) -> fmt::Result
+        where F: FnMut(&str) -> fmt::Result
+    {
+        // Output is split only at ASCII characters, so each piece is valid UTF-8.
+        let mut emit = |s: &[u8]| emit(str::from_utf8(s).unwrap());
+        let words = mn_words_required(self.src);
+        for n in 0..words {
+            let case = match self.format {
+                Some(format) => {
+                    format.write_separator(n, &mut emit)?;
+                    format.case(n)
+                }
+                None => {
+                    write_default_separator(n, &mut emit)?;
+                    Case::Lower
+                }
+            };
+            let case = if upper { Case::Upper } else { case };
+            case.write(mn_encode_word(self.src, n), &mut emit)?;
+        }
+        match self.format {
+            Some(format) => format.finish(words, &mut emit),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Pass the separator before word number `n` in the default format, `x-x-x--`, to `emit`.
+///
+/// This is the same as [`Format::write_separator`] for [`Format::default`], which would have to
+/// be built (and allocated) each time a [`Display`] without a format is written.
+fn write_default_separator<F>(n: usize, emit: &mut F) -> fmt::Result
+    where F: FnMut(&[u8]) -> fmt::Result
+{
+    match n {
+        0 => Ok(()),
+        n if n % 3 == 0 => emit(b"--"),
+        _ => emit(b"-"),
+    }
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let upper = f.alternate();
+        let width = match f.width() {
+            Some(width) => width,
+            None => return self.write(upper, |s| f.write_str(s)),
+        };
+
+        let mut len = 0;
+        self.write(upper, |s| {
+            len += s.chars().count();
+            Ok(())
+        })?;
+        let padding = width.saturating_sub(len);
+        let (before, after) = match f.align() {
+            Some(Alignment::Right) => (padding, 0),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        self.write(upper, |s| f.write_str(s))?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// An allocator that counts the allocations made by each thread.
+    struct Counting;
+
+    std::thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    const SRC: [u8; 8] = [101, 2, 240, 6, 108, 11, 20, 97];
+
+    #[test]
+    fn test_display() {
+        assert_eq!(display(&SRC).to_string(), crate::to_string(SRC));
+        assert_eq!(display(&SRC[..0]).to_string(), "");
+        assert_eq!(format!("{:#}", display(&SRC)), "DIGITAL-APOLLO-AROMA--RIVAL-ARTIST-REBEL");
+
+        let format = Format::new("%n. Xx ").unwrap();
+        assert_eq!(display(&SRC[..4]).format(&format).to_string(), "1. Digital 2. Apollo 3. Aroma");
+        assert_eq!(format!("{:#}", display(&SRC[..2]).format(&format)), "1. RADIO 2. ACADEMY");
+    }
+
+    #[test]
+    fn test_no_allocation() {
+        let mnemonic = crate::Mnemonic::new(SRC);
+        let mut s = String::with_capacity(200);
+        let before = ALLOCATIONS.with(Cell::get);
+        write!(s, "{} {:#} {:*^50}", display(&SRC), display(&SRC[..5]), display(&SRC)).unwrap();
+        write!(s, " {}", mnemonic).unwrap();
+        assert_eq!(ALLOCATIONS.with(Cell::get), before);
+        assert_eq!(s, format!("{0} {1} {2:*^50} {0}", crate::to_string(SRC),
+                              crate::to_string(&SRC[..5]).to_uppercase(), crate::to_string(SRC)));
+    }
+
+    #[test]
+    fn test_width() {
+        let s = display(&SRC[..4]);
+        assert_eq!(format!("{:10}", s), "digital-apollo-aroma");
+        assert_eq!(format!("{:24}|", s), "digital-apollo-aroma    |");
+        assert_eq!(format!("{:*^24}", s), "**digital-apollo-aroma**");
+        assert_eq!(format!("{:>#23}", s), "   DIGITAL-APOLLO-AROMA");
+
+        let format = Format::new("x\u{2022}").unwrap();
+        assert_eq!(format!("{:>15}", display(&SRC[..2]).format(&format)), "  radio\u{2022}academy");
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::result;
+use core::str;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::wordlist::mn_check_wordlist;
+use crate::{mn_encode_with, mn_encode_word_index, mn_words_required};
+use crate::{Case, DefaultWordlist, Format, Result, Wordlist};
+
+/// An encoder with configurable output style.
+///
+/// By default, an `Encoder` writes the same output as [`encode`](crate::encode), using the
+/// default [`Format`].  Its methods change the format, and can be chained:
+///
+/// ```
+/// use mnemonic::{Case, Encoder};
+///
+/// let encoder = Encoder::new()
+///     .case(Case::Capitalized)
+///     .separator(" ").unwrap()
+///     .group_separator(" / ").unwrap()
+///     .line_width(30);
+///
+/// let s = encoder.encode_to_string([101, 2, 240, 6, 108, 11, 20, 97]);
+/// assert_eq!(s, "Digital Apollo Aroma / Rival\nArtist Rebel");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Encoder<L = DefaultWordlist> {
+    wordlist: L,
+    format: Format,
+    line_width: Option<usize>,
+}
+
+impl Encoder {
+    /// An encoder with the default settings.
+    pub fn new() -> Self {
+        Encoder::default()
+    }
+}
+
+impl<L: Wordlist> Encoder<L> {
+    /// Write words from `wordlist` instead of the standard word list.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+    pub fn wordlist<M: Wordlist>(self, wordlist: M) -> Encoder<M> {
+        mn_check_wordlist(&wordlist);
+        Encoder { wordlist, format: self.format, line_width: self.line_width }
+    }
+
+    /// Write words in the given format.
+    ///
+    /// This replaces any case or separators set by earlier calls.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Write every word in `case`, instead of the case of its slot in the format.
+    pub fn case(mut self, case: Case) -> Self {
+        self.format.set_case(case);
+        self
+    }
+
+    /// Put `separator` between the words in each group, instead of the separators in the
+    /// format.
+    ///
+    /// Returns [`Error::InvalidFormat`](crate::Error::InvalidFormat) if `separator` is empty or
+    /// contains ASCII letters, since the words could then not be told apart from the separators.
+    pub fn separator(mut self, separator: &str) -> Result<Self> {
+        self.format.set_separator(separator)?;
+        Ok(self)
+    }
+
+    /// Put `separator` between groups of words, instead of the separator at the end of the
+    /// format.
+    ///
+    /// Returns [`Error::InvalidFormat`](crate::Error::InvalidFormat) if `separator` contains
+    /// ASCII letters.
+    pub fn group_separator(mut self, separator: &str) -> Result<Self> {
+        self.format.set_group_separator(separator)?;
+        Ok(self)
+    }
+
+    /// Start a new line instead of writing a separator wherever the next word would make the
+    /// line longer than `width` characters.
+    ///
+    /// The line break replaces the first run of whitespace in the separator, or follows the
+    /// separator if it has no whitespace.  A word is never split, so a line may be longer than
+    /// `width` if a single word does not fit.  Wrapped output can be read by
+    /// [`decode`](crate::decode), but not by [`Format::decode`].
+    pub fn line_width(mut self, width: usize) -> Self {
+        self.line_width = Some(width);
+        self
+    }
+
+    /// Encode the bytes of `src`, and write the string to `dest`.
+    #[cfg(feature = "std")]
+    pub fn encode_to_writer<S, W>(&self, src: S, mut dest: W) -> io::Result<()>
+        where S: AsRef<[u8]>,
+              W: Write
+    {
+        self.mn_encode(src.as_ref(), |s| dest.write_all(s))
+    }
+
+    /// Encode the bytes of `src` and return the results as a `String`.
+    pub fn encode_to_string<S: AsRef<[u8]>>(&self, src: S) -> String {
+        let mut s = String::new();
+        self.encode_to_fmt(src, &mut s).unwrap();
+        s
+    }
+
+    /// Encode the bytes of `src`, and write the string to a [`fmt::Write`].
+    pub fn encode_to_fmt<S, W>(&self, src: S, mut dest: W) -> fmt::Result
+        where S: AsRef<[u8]>,
+              W: fmt::Write
+    {
+        // Output is split only at ASCII characters, so each piece is valid UTF-8.
+        self.mn_encode(src.as_ref(), |s| dest.write_str(str::from_utf8(s).unwrap()))
+    }
+
+    /// Pass each piece of the encoding of `src` to `emit`.
+    fn mn_encode<E, F>(&self, src: &[u8], mut emit: F) -> result::Result<(), E>
+        where F: FnMut(&[u8]) -> result::Result<(), E>
+    {
+        let base = self.wordlist.base();
+        let width = match self.line_width {
+            Some(width) => width,
+            None => {
+                return mn_encode_with(src, &self.format, base, None,
+                                      |index| self.wordlist.word(index), emit)
+            }
+        };
+        let mut column = 0;
+        let words = mn_words_required(src);
+        let mut separator = self.format.separator(0);
+        for n in 0..words {
+            let next = if n + 1 < words { self.format.separator(n + 1) } else { Vec::new() };
+            let word = self.wordlist.word(mn_encode_word_index(src, n, base));
+            // Leave room for any part of the next separator that would end this line.
+            let overhang = match next.contains(&b'\n') {
+                true => 0,
+                false => chars(&next[..line_break(&next).start]),
+            };
+
+            let mut rest = &separator[..];
+            if n > 0 && !rest.contains(&b'\n')
+                && column + chars(rest) + word.len() + overhang > width
+            {
+                let line_break = line_break(rest);
+                emit(&rest[..line_break.start])?;
+                emit(b"\n")?;
+                column = 0;
+                rest = &rest[line_break.end..];
+            }
+            emit(rest)?;
+            column = match rest.iter().rposition(|&c| c == b'\n') {
+                Some(i) => chars(&rest[i + 1..]),
+                None => column + chars(rest),
+            };
+            self.format.case(n).write(word, &mut emit)?;
+            column += word.len();
+            separator = next;
+        }
+        self.format.finish(words, &mut emit)
+    }
+}
+
+/// The part of `separator` to replace with a line break: its first run of whitespace, or an
+/// empty range at its end.
+fn line_break(separator: &[u8]) -> Range<usize> {
+    let start = separator.iter().position(u8::is_ascii_whitespace).unwrap_or(separator.len());
+    let spaces = separator[start..].iter().take_while(|c| c.is_ascii_whitespace()).count();
+    start..start + spaces
+}
+
+/// The number of characters in the UTF-8 string `s`.
+fn chars(s: &[u8]) -> usize {
+    s.iter().filter(|&&c| (c as i8) >= -0x40).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    const SRC: [u8; 8] = [101, 2, 240, 6, 108, 11, 20, 97];
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Encoder::new().encode_to_string(SRC), crate::to_string(SRC));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_encode_to_writer() {
+        let mut dest = Vec::new();
+        Encoder::new().line_width(20).encode_to_writer(SRC, &mut dest).unwrap();
+        assert_eq!(dest, b"digital-apollo-\naroma--rival-artist-\nrebel");
+    }
+
+    #[test]
+    fn test_style() {
+        let encoder = Encoder::new().case(Case::Upper).separator(".").unwrap()
+                                    .group_separator(" % ").unwrap();
+        assert_eq!(encoder.encode_to_string(SRC), "DIGITAL.APOLLO.AROMA % RIVAL.ARTIST.REBEL");
+        assert_eq!(encoder.format.as_str(), "X.X.X %% ");
+
+        let format = Format::new("%n. Xx %2l").unwrap();
+        let encoder = Encoder::new().format(format).separator("!").unwrap().case(Case::Lower);
+        assert_eq!(encoder.format.as_str(), "%n. x %2l");
+        assert_eq!(encoder.encode_to_string(SRC),
+                   "1. digital 2. apollo\n3. aroma 4. rival\n5. artist 6. rebel");
+    }
+
+    #[test]
+    fn test_separator_letters() {
+        let e = Encoder::new().separator(" and ").unwrap_err();
+        assert_eq!(e.to_string(), "invalid format ' and ': separator must not contain letters");
+        let e = Encoder::new().group_separator("/x/").unwrap_err();
+        assert!(matches!(e, Error::InvalidFormat { .. }));
+        let e = Encoder::new().separator("").unwrap_err();
+        assert_eq!(e.to_string(), "invalid format '': separator must not be empty");
+        assert_eq!(Encoder::new().group_separator("").unwrap().format.as_str(), "x-x-x");
+    }
+
+    #[test]
+    fn test_wordlist() {
+        let encoder = Encoder::new().wordlist(crate::wordlist::tests::Reversed)
+                                    .separator(" ").unwrap();
+        assert_eq!(encoder.encode_to_string([0x01, 0xE2, 0x40]), "needle russian fax");
+        let encoder = encoder.line_width(12);
+        assert_eq!(encoder.encode_to_string([0x01, 0xE2, 0x40]), "needle\nrussian fax");
+    }
+
+    #[test]
+    fn test_line_width() {
+        let encode = |encoder: Encoder, width| encoder.line_width(width).encode_to_string(SRC);
+        assert_eq!(encode(Encoder::new(), 20), "digital-apollo-\naroma--rival-artist-\nrebel");
+        assert_eq!(encode(Encoder::new(), 0), "digital-\napollo-\naroma--\nrival-\nartist-\nrebel");
+        let encoder = Encoder::new().separator(" ").unwrap().group_separator("  ").unwrap();
+        assert_eq!(encode(encoder, 14),
+                   "digital apollo\naroma  rival\nartist rebel");
+
+        let format = Format::new("%n. Xx ").unwrap();
+        assert_eq!(encode(Encoder::new().format(format), 24),
+                   "1. Digital 2. Apollo\n3. Aroma 4. Rival\n5. Artist 6. Rebel");
+        let format = Format::new("x x\n").unwrap();
+        assert_eq!(encode(Encoder::new().format(format), 14),
+                   "digital apollo\naroma rival\nartist rebel");
+
+        for width in 0..50 {
+            let encoder = Encoder::new().separator(" ").unwrap().group_separator(" - ").unwrap();
+            let s = encode(encoder, width);
+            assert_eq!(crate::decode_to_vec(s).unwrap(), SRC);
+        }
+    }
+}
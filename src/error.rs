@@ -0,0 +1,214 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::result;
+#[cfg(feature = "std")]
+use std::error::Error as ErrorTrait;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Errors returned by mnemonic decoding, or by parsing a [`Format`](crate::Format).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// A word that is not in the word list.
+    UnrecognizedWord { word: String, position: Position },
+    /// The input ended with an incomplete encoding (possibly a truncated string).
+    UnexpectedRemainder,
+    /// A 24-bit remainder word anywhere other than the third word of a chunk.
+    UnexpectedRemainderWord { word: String, position: Position },
+    /// A word following a 24-bit remainder word.
+    DataPastRemainder { word: String, position: Position },
+    /// A word index that is out of range for the word list.
+    InvalidIndex { index: usize, position: Position },
+    /// A word that would cause a chunk to overflow 32 bits.
+    InvalidEncoding { word: String, position: Position },
+    /// A word that could be corrected to more than one word in the word list.
+    AmbiguousWord { word: String, position: Position, candidates: Vec<String> },
+    /// The check words did not match the checksum of the decoded data.
+    ChecksumMismatch,
+    /// There were too many missing or wrong words to correct with the parity words.
+    Uncorrectable,
+    /// An encoding template that could not be parsed.
+    InvalidFormat { template: String, reason: &'static str },
+    /// A separator that does not match the format, when decoding with a [`Format`](crate::Format).
+    ///
+    /// `position` is that of the word following the separator, or the number of words for a
+    /// separator at the end, with the byte range of the separator itself.
+    UnexpectedSeparator { separator: String, expected: String, position: Position },
+    /// A group of words with a missing or extra word, when decoding with a
+    /// [`Format`](crate::Format).
+    ///
+    /// `group` counts from zero, and `position` is that of the first word in the group.
+    WrongGroupSize { group: usize, words: usize, expected: usize, position: Position },
+    /// The decoded data would be longer than the limit set with
+    /// [`Decoder::max_len`](crate::Decoder::max_len).
+    TooLong { max_len: usize },
+    /// The decoded data did not fit in the output buffer.
+    BufferTooSmall { capacity: usize },
+    /// The decoded data was not the expected length.
+    WrongLength { expected: usize, len: usize },
+    /// The input did not have the expected number of words.
+    WrongWordCount { expected: usize, words: usize },
+    /// A word that is not needed, in a variable-length integer that has a shorter encoding.
+    NonCanonical { word: String, position: Position },
+}
+use Error::*;
+
+/// Result type returned by mnemonic decoding.
+pub type Result<T> = result::Result<T, Error>;
+
+/// The location of a word in the input to a decoder.
+///
+/// `word` is a zero-based index, but is displayed counting from one, as in "word 1" for the
+/// first word.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// The index of the word, counting from zero.
+    pub word: usize,
+    /// The byte range of the word within the input string, if the input was a string.
+    pub bytes: Option<Range<usize>>,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "word {}", self.word + 1)?;
+        if let Some(ref bytes) = self.bytes {
+            write!(f, " (bytes {}..{})", bytes.start, bytes.end)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error caused by a single word, before its text and position are attached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WordError {
+    UnrecognizedWord,
+    UnexpectedRemainderWord,
+    DataPastRemainder,
+    InvalidEncoding,
+    NonCanonical,
+}
+
+impl WordError {
+    /// Convert to an [`Error`] reporting `word` at `position`.
+    pub(crate) fn at(self, word: &[u8], position: Position) -> Error {
+        let word = String::from_utf8_lossy(word).into_owned();
+        match self {
+            WordError::UnrecognizedWord => UnrecognizedWord { word, position },
+            WordError::UnexpectedRemainderWord => UnexpectedRemainderWord { word, position },
+            WordError::DataPastRemainder => DataPastRemainder { word, position },
+            WordError::InvalidEncoding => InvalidEncoding { word, position },
+            WordError::NonCanonical => NonCanonical { word, position },
+        }
+    }
+}
+
+impl Error {
+    /// An [`AmbiguousWord`] error reporting that `word` at `position` could be any of
+    /// `candidates`.
+    pub(crate) fn ambiguous<'a, I>(word: &[u8], position: &Position, candidates: I) -> Error
+        where I: IntoIterator<Item = &'a [u8]>
+    {
+        AmbiguousWord {
+            word: String::from_utf8_lossy(word).into_owned(),
+            position: position.clone(),
+            candidates: candidates.into_iter()
+                                  .map(|c| String::from_utf8_lossy(c).into_owned())
+                                  .collect(),
+        }
+    }
+
+    /// The position of the word that caused this error, if there is one.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn position_mut(&mut self) -> Option<&mut Position> {
+        match self {
+            UnrecognizedWord { position, .. } |
+            UnexpectedRemainderWord { position, .. } |
+            DataPastRemainder { position, .. } |
+            InvalidEncoding { position, .. } |
+            InvalidIndex { position, .. } |
+            AmbiguousWord { position, .. } |
+            UnexpectedSeparator { position, .. } |
+            WrongGroupSize { position, .. } |
+            NonCanonical { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(other: io::Error) -> Self {
+        // Unwrap decoding errors that were passed through an `io::Read` implementation.
+        if other.get_ref().is_some_and(|e| e.is::<Error>()) {
+            return *other.into_inner().unwrap().downcast().unwrap()
+        }
+        Io(other)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for io::Error {
+    fn from(other: Error) -> Self {
+        match other {
+            Io(e) => e,
+            e @ InvalidFormat { .. } => io::Error::new(io::ErrorKind::InvalidInput, e),
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ErrorTrait for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Io(ref e) => write!(f, "{}", e),
+            UnrecognizedWord { word, position } =>
+                write!(f, "unrecognized word '{}' at {}", word, position),
+            UnexpectedRemainder => f.write_str("unexpected remainder (possible truncated string)"),
+            UnexpectedRemainderWord { word, position } =>
+                write!(f, "unexpected 24-bit remainder word '{}' at {}", word, position),
+            DataPastRemainder { word, position } =>
+                write!(f, "unexpected word '{}' past 24-bit remainder at {}", word, position),
+            InvalidEncoding { word, position } =>
+                write!(f, "invalid encoding: word '{}' at {} is out of range", word, position),
+            InvalidIndex { index, position } =>
+                write!(f, "word index {} at {} is out of range", index, position),
+            ChecksumMismatch => f.write_str("checksum mismatch"),
+            Uncorrectable => f.write_str("too many errors to correct"),
+            InvalidFormat { template, reason } => write!(f, "invalid format '{}': {}", template, reason),
+            UnexpectedSeparator { separator, expected, position } =>
+                write!(f, "unexpected separator '{}' before {}: expected '{}'",
+                       separator.escape_debug(), position, expected.escape_debug()),
+            TooLong { max_len } => write!(f, "decoded data is longer than {} bytes", max_len),
+            BufferTooSmall { capacity } =>
+                write!(f, "decoded data does not fit in a buffer of {} bytes", capacity),
+            WrongLength { expected, len } =>
+                write!(f, "expected {} bytes of decoded data, found {}", expected, len),
+            WrongWordCount { expected, words } =>
+                write!(f, "expected {} words, found {}", expected, words),
+            NonCanonical { word, position } =>
+                write!(f, "non-canonical encoding: unneeded word '{}' at {}", word, position),
+            WrongGroupSize { group, words, expected, position } =>
+                write!(f, "expected {} words in group {} starting at {}, found {}",
+                       expected, group + 1, position, words),
+            AmbiguousWord { word, position, candidates } => {
+                write!(f, "ambiguous word '{}' at {}: could be ", word, position)?;
+                for (i, candidate) in candidates.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "'{}'", candidate)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,405 @@
+//! Reed-Solomon error correction over word indices.
+//!
+//! Word indices are treated as symbols in the prime field GF(1627).  Every element of the field
+//! is the index of a word (0 to 1625 for ordinary words, and 1626 for the first remainder
+//! word), so parity symbols can always be written as words.  The data words are unchanged, so
+//! an encoding with parity words starts with the ordinary encoding of the same data.
+//!
+//! The 24-bit remainder words have indices outside the field.  Instead, the last data word is
+//! replaced by its digit (its index minus 1626), and an extra message symbol that is not written
+//! out records whether the last word was a remainder word.  The decoder can recover this flag
+//! from the last word itself, unless that word is missing, in which case it counts as one more
+//! unknown symbol.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use std::io::{self, Write};
+
+use crate::{mn_decode_indices, mn_encode_with, mn_encode_word_index, mn_word_index,
+            mn_words_required, Correction, Format, Position, Result, MN_BASE, MN_REMAINDER,
+            MN_WORDS};
+use crate::Error::*;
+
+/// The order of the field.
+const P: u32 = 1627;
+
+/// A primitive element of the field.
+const ALPHA: u32 = 3;
+
+/// The maximum number of data and parity words.  Each word, plus the remainder flag, needs a
+/// distinct power of `ALPHA`.
+const MAX_WORDS: usize = P as usize - 2;
+
+/// Encode the bytes of `src` with a custom template, followed by `parity_words` words of
+/// Reed-Solomon parity.
+///
+/// Decode the result with [`decode_with_parity`], using the same number of parity words.  With
+/// `n` parity words, the decoder can fill in up to `n` missing or illegible words, or correct up
+/// to `n / 2` wrong words whose positions are not known.  If the data ends with a 24-bit
+/// remainder word, losing that word costs as much as losing two words.
+///
+/// The total number of data and parity words is limited to 1625, enough for about 2 kB of
+/// data.  Longer input is rejected with an error of kind
+/// [`InvalidInput`](io::ErrorKind::InvalidInput).
+///
+/// ## Example
+///
+/// ```
+/// let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+/// let mut dest = Vec::<u8>::new();
+///
+/// mnemonic::encode_with_parity(&bytes, mnemonic::MN_FDEFAULT, 2, &mut dest).unwrap();
+/// assert_eq!(dest, &b"digital-apollo-aroma--rival-artist-rebel--game-jazz"[..]);
+/// ```
+pub fn encode_with_parity<S, F, W>(src: S, format: F, parity_words: usize, mut dest: W)
+    -> io::Result<()>
+    where S: AsRef<[u8]>,
+          F: AsRef<[u8]>,
+          W: Write
+{
+    let (src, format) = (src.as_ref(), Format::from_bytes(format.as_ref())?);
+    let indices: Vec<u32> = (0..mn_words_required(src)).map(|n| mn_encode_word_index(src, n, MN_BASE))
+                                                       .collect();
+    if indices.len() + parity_words > MAX_WORDS {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  "input too long for error correction"))
+    }
+    let parity = fec_parity(&fec_message(&indices), parity_words);
+    mn_encode_with(src, &format, MN_BASE, parity,
+                   |index| MN_WORDS[index as usize],
+                   |s| dest.write_all(s))
+}
+
+/// Decode a mnemonic string produced by [`encode_with_parity`], correcting missing or wrong
+/// words, and write the bytes to `dest`.
+///
+/// Words that are unknown may be written as `?` (or any word containing a `?`).  These, and any
+/// words that are not in the word list, are treated as missing.  Valid words that are wrong are
+/// located and corrected using the parity words.  If there are too many errors to correct,
+/// [`Error::Uncorrectable`](crate::Error::Uncorrectable) is returned.
+///
+/// Returns the corrections that were made.
+///
+/// ## Example
+///
+/// ```
+/// let src = "digital-?-aroma--rival-artist-rebel--game-jazz";
+///
+/// let mut dest = Vec::<u8>::new();
+/// let corrections = mnemonic::decode_with_parity(src, 2, &mut dest).unwrap();
+///
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// assert_eq!(corrections[0].corrected, "apollo");
+///
+/// let wrong = "digital-rival-aroma--rival-artist-rebel--game-jazz";
+/// let corrections = mnemonic::decode_with_parity(wrong, 2, &mut Vec::new()).unwrap();
+/// assert_eq!(corrections[0].corrected, "apollo");
+/// ```
+pub fn decode_with_parity<S, W>(src: S, parity_words: usize, mut dest: W) -> Result<Vec<Correction>>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    let src = src.as_ref();
+    let t = parity_words;
+    let tokens: Vec<_> = fec_tokens(src).collect();
+    if tokens.len() < t || tokens.len() > MAX_WORDS {
+        return Err(Uncorrectable)
+    }
+    let n = tokens.len() - t; // number of data words
+    let has_remainder = n > 0 && n % 3 == 0;
+
+    // The received word indices, or None for missing words.
+    let received: Vec<Option<u32>> = tokens.iter().map(|&(_, word)| mn_word_index(word)).collect();
+
+    // Codeword positions: parity symbols first, then the data symbols, then the remainder flag.
+    let mut codeword = vec![0; t + n + 1];
+    let mut erasures = Vec::new();
+    for (k, &index) in received.iter().enumerate() {
+        let pos = if k < n { t + k } else { k - n };
+        match index {
+            Some(i) if k < n && i < MN_BASE => codeword[pos] = i,
+            Some(i) if has_remainder && k == n - 1 => codeword[pos] = i - MN_BASE,
+            Some(i) if k >= n && i < P => codeword[pos] = i,
+            _ => erasures.push(pos),
+        }
+    }
+    if has_remainder {
+        match received[n - 1] {
+            Some(i) => codeword[t + n] = (i >= MN_BASE) as u32,
+            None => erasures.push(t + n),
+        }
+    }
+    fec_correct(&mut codeword, &erasures, t).ok_or(Uncorrectable)?;
+
+    // Convert the corrected symbols back to word indices.
+    let flag = codeword[t + n];
+    if flag > 1 || (flag == 1 && !has_remainder) {
+        return Err(Uncorrectable)
+    }
+    let mut corrected = Vec::with_capacity(n + t);
+    for k in 0..n {
+        let remainder_word = flag == 1 && k == n - 1;
+        corrected.push(match codeword[t + k] {
+            digit if remainder_word && digit < MN_REMAINDER as u32 => digit + MN_BASE,
+            digit if !remainder_word && digit < MN_BASE => digit,
+            _ => return Err(Uncorrectable),
+        });
+    }
+    corrected.extend_from_slice(&codeword[..t]);
+
+    let position = |k: usize| {
+        let (start, word) = tokens[k];
+        Position { word: k, bytes: Some(start..start + word.len()) }
+    };
+    let mut corrections = Vec::new();
+    for (k, &index) in corrected.iter().enumerate() {
+        if received[k] != Some(index) {
+            corrections.push(Correction::new(tokens[k].1, MN_WORDS[index as usize], &position(k)));
+        }
+    }
+
+    let indices = corrected[..n].iter().enumerate()
+                                .map(|(k, &index)| Ok((index, MN_WORDS[index as usize], position(k))));
+    mn_decode_indices(indices, MN_BASE, |bytes| Ok(dest.write_all(bytes)?))?;
+    Ok(corrections)
+}
+
+/// Split `src` into words at bytes that are neither alphabetic nor `?`, yielding the byte
+/// offset of each word along with the word itself.
+fn fec_tokens(src: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    src.split(|&c| !c.is_ascii_alphabetic() && c != b'?')
+       .filter(|w| !w.is_empty())
+       .map(move |w| (w.as_ptr() as usize - src.as_ptr() as usize, w))
+}
+
+/// The message symbols for a sequence of word indices: the digit of each word, followed by a
+/// flag that is 1 if the last word is a remainder word.
+fn fec_message(indices: &[u32]) -> Vec<u32> {
+    let mut message: Vec<u32> = indices.iter().map(|&i| i % MN_BASE).collect();
+    message.push(indices.last().map_or(0, |&i| (i >= MN_BASE) as u32));
+    message
+}
+
+/// Compute `t` parity symbols for `message`.
+///
+/// The codeword is the parity followed by the message, as coefficients of a polynomial in order
+/// of increasing degree.  It is divisible by the generator polynomial `(x - α)...(x - α^t)`.
+fn fec_parity(message: &[u32], t: usize) -> Vec<u32> {
+    let mut generator = vec![1];
+    for j in 1..=t {
+        generator = poly_mul(&generator, &[neg(pow(ALPHA, j as u32)), 1]);
+    }
+    let mut r: Vec<u32> = vec![0; t];
+    r.extend_from_slice(message);
+    for i in (t..r.len()).rev() {
+        let coef = r[i];
+        for (j, &g) in generator.iter().enumerate() {
+            r[i - t + j] = sub(r[i - t + j], mul(coef, g));
+        }
+    }
+    r.truncate(t);
+    r.iter().map(|&x| neg(x)).collect()
+}
+
+/// Correct `codeword` in place, given the positions of known erasures and the number `t` of
+/// parity symbols.  Returns `None` if the errors cannot be corrected.
+///
+/// This is the Berlekamp-Massey algorithm initialized with the erasure locator, followed by a
+/// Chien search for the error positions and Forney's formula for the error values.
+fn fec_correct(codeword: &mut [u32], erasures: &[usize], t: usize) -> Option<()> {
+    if erasures.len() > t {
+        return None
+    }
+    let syndromes = fec_syndromes(codeword, t);
+    if syndromes.iter().all(|&s| s == 0) {
+        return Some(())
+    }
+
+    let mut erasure_locator = vec![1];
+    for &pos in erasures {
+        erasure_locator = poly_mul(&erasure_locator, &[1, neg(alpha_pow(pos))]);
+    }
+    let f = erasures.len();
+    let mut locator = erasure_locator.clone();
+    let mut b = erasure_locator;
+    let mut len = f;
+    for r in f + 1..=t {
+        let delta = locator.iter().enumerate().take(r)
+                           .fold(0, |sum, (i, &l)| add(sum, mul(l, syndromes[r - i - 1])));
+        b.insert(0, 0); // b = x * b
+        if delta != 0 {
+            let next = poly_sub(&locator, &poly_scale(&b, delta));
+            if 2 * len < r + f {
+                b = poly_scale(&locator, inv(delta));
+                len = r + f - len;
+            }
+            locator = next;
+        }
+    }
+    while locator.last() == Some(&0) {
+        locator.pop();
+    }
+    if locator.len() != len + 1 {
+        return None
+    }
+
+    let positions: Vec<usize> = (0..codeword.len())
+        .filter(|&i| poly_eval(&locator, inv(alpha_pow(i))) == 0)
+        .collect();
+    if positions.len() != len {
+        return None
+    }
+
+    let mut evaluator = poly_mul(&syndromes, &locator);
+    evaluator.truncate(t);
+    let derivative: Vec<u32> = locator.iter().enumerate().skip(1)
+                                      .map(|(i, &l)| mul(i as u32 % P, l))
+                                      .collect();
+    for pos in positions {
+        let x_inv = inv(alpha_pow(pos));
+        let denominator = poly_eval(&derivative, x_inv);
+        if denominator == 0 {
+            return None
+        }
+        let error = neg(mul(poly_eval(&evaluator, x_inv), inv(denominator)));
+        codeword[pos] = sub(codeword[pos], error);
+    }
+    if fec_syndromes(codeword, t).iter().any(|&s| s != 0) {
+        return None
+    }
+    Some(())
+}
+
+/// Evaluate `codeword` at `α, α^2, ..., α^t`.
+fn fec_syndromes(codeword: &[u32], t: usize) -> Vec<u32> {
+    (1..=t).map(|j| poly_eval(codeword, alpha_pow(j))).collect()
+}
+
+fn add(a: u32, b: u32) -> u32 { (a + b) % P }
+fn sub(a: u32, b: u32) -> u32 { (a + P - b) % P }
+fn neg(a: u32) -> u32 { (P - a) % P }
+fn mul(a: u32, b: u32) -> u32 { a * b % P }
+fn inv(a: u32) -> u32 { pow(a, P - 2) }
+
+fn pow(mut a: u32, mut e: u32) -> u32 {
+    let mut result = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul(result, a);
+        }
+        a = mul(a, a);
+        e >>= 1;
+    }
+    result
+}
+
+fn alpha_pow(i: usize) -> u32 {
+    pow(ALPHA, (i % (P as usize - 1)) as u32)
+}
+
+/// Evaluate a polynomial, given its coefficients in order of increasing degree.
+fn poly_eval(p: &[u32], x: u32) -> u32 {
+    p.iter().rev().fold(0, |acc, &c| add(mul(acc, x), c))
+}
+
+fn poly_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            product[i + j] = add(product[i + j], mul(x, y));
+        }
+    }
+    product
+}
+
+fn poly_sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    (0..a.len().max(b.len()))
+        .map(|i| sub(a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0)))
+        .collect()
+}
+
+fn poly_scale(p: &[u32], k: u32) -> Vec<u32> {
+    p.iter().map(|&c| mul(c, k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+    use crate::Error;
+
+    fn encode(src: &[u8], parity_words: usize) -> Vec<String> {
+        let mut encoded = Vec::new();
+        encode_with_parity(src, "x ", parity_words, &mut encoded).unwrap();
+        String::from_utf8(encoded).unwrap().split_whitespace().map(String::from).collect()
+    }
+
+    fn decode(words: &[String], parity_words: usize) -> Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        decode_with_parity(words.join(" "), parity_words, &mut decoded)?;
+        Ok(decoded)
+    }
+
+    #[test]
+    fn test_alpha_is_primitive() {
+        for q in [2, 3, 271] {
+            assert_ne!(pow(ALPHA, (P - 1) / q), 1);
+        }
+    }
+
+    #[test]
+    fn test_parity_is_codeword() {
+        let message = fec_message(&[5, 1000, 1627]);
+        let mut codeword = fec_parity(&message, 4);
+        codeword.extend_from_slice(&message);
+        assert!(fec_syndromes(&codeword, 4).iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_remainder_word_erased() {
+        let src = [0x01, 0xE2, 0x40];
+        let mut words = encode(&src, 2);
+        assert_eq!(words[..3], ["consul", "quiet", "fax"]);
+        words[2] = String::from("?");
+        assert_eq!(decode(&words, 2).unwrap(), src);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut words = encode(&[], 2);
+        assert_eq!(words.len(), 2);
+        assert_eq!(decode(&words, 2).unwrap(), []);
+        words[1] = String::from("?");
+        assert_eq!(decode(&words, 2).unwrap(), []);
+        assert!(matches!(decode(&words[..1], 2), Err(Error::Uncorrectable)));
+    }
+
+    #[test]
+    fn test_too_many_errors() {
+        let mut words = encode(&[1, 2, 3, 4, 5, 6, 7, 8], 2);
+        words[0] = String::from("?");
+        words[3] = String::from("?");
+        words[5] = String::from("?");
+        assert!(matches!(decode(&words, 2), Err(Error::Uncorrectable)));
+    }
+
+    quickcheck! {
+        fn quickcheck_erasures(src: Vec<u8>, erased: Vec<usize>) -> bool {
+            let mut words = encode(&src, 4);
+            for i in erased.iter().take(3) {
+                let i = i % words.len();
+                words[i] = String::from("?");
+            }
+            decode(&words, 4).unwrap() == src
+        }
+
+        fn quickcheck_errors(src: Vec<u8>, errors: Vec<(usize, usize)>) -> bool {
+            let mut words = encode(&src, 6);
+            for &(i, w) in errors.iter().take(2) {
+                let i = i % words.len();
+                words[i] = String::from_utf8(MN_WORDS[w % 1626].to_vec()).unwrap();
+            }
+            decode(&words, 6).unwrap() == src
+        }
+    }
+}
@@ -0,0 +1,801 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{self, Write as _};
+use core::mem;
+use core::ops::Range;
+use core::result;
+use core::str::{self, FromStr};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::error::WordError;
+use crate::{mn_decode_indices, mn_encode, mn_resolve_exact, mn_words, Error, Position, Result};
+use crate::{words_required, MN_BASE, MN_FDEFAULT_STR, MN_MAX_WORD_LEN};
+
+/// A template for formatting encoded words, parsed and checked ahead of time.
+///
+/// In a template, each run of ASCII letters is a slot for one word, and everything else is
+/// copied to the output as a separator.  The template is repeated as many times as needed, and
+/// any separator after the last slot is written only when it is followed by another word.  For
+/// example, the default template `"x-x-x--"` puts a dash between words and a double dash
+/// between groups of three words:
+///
+/// ```
+/// let format = mnemonic::Format::new("x-x-x--").unwrap();
+///
+/// let mut s = String::new();
+/// format.encode_to_fmt([101, 2, 240, 6, 108, 11, 20, 97], &mut s).unwrap();
+/// assert_eq!(s, "digital-apollo-aroma--rival-artist-rebel");
+/// ```
+///
+/// The letters in a slot set the case of its word: a lowercase slot like `x` writes the word in
+/// lowercase, an uppercase slot like `X` writes it in uppercase, and a capitalized slot like
+/// `Xx` capitalizes its first letter.  Other mixtures of case are rejected.
+///
+/// Separators may contain these directives:
+///
+/// * `%n` is replaced by the number of the following word, counting from 1.  After the last
+///   slot in the template, it is the number of the preceding word instead.
+/// * `%%` is a literal `%`.
+///
+/// These directives may appear anywhere in the template, and change how it is repeated:
+///
+/// * `%Nl`, where `N` is a positive number, starts a new line after every `N` repetitions of
+///   the template, in place of the separator after the last slot.
+/// * `%t` writes the separator after the last word, up to the next slot or the end of the
+///   template, instead of leaving it out.
+///
+/// ```
+/// let format = mnemonic::Format::new("%n. Xx %2l").unwrap();
+///
+/// let mut s = String::new();
+/// format.encode_to_fmt([101, 2, 240, 6], &mut s).unwrap();
+/// assert_eq!(s, "1. Digital 2. Apollo\n3. Aroma");
+/// ```
+///
+/// A template must contain at least one word slot.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Format {
+    template: String,
+    /// The slots in one repetition of the template.
+    slots: Vec<Slot>,
+    /// The separator after the last slot.
+    tail: Vec<Piece>,
+    /// The number of repetitions of the template per line, or 0 for no line breaks.
+    line_groups: usize,
+    /// Whether to write the separator after the last word.
+    trailing: bool,
+}
+
+/// A word slot in a template.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Slot {
+    /// The separator before the word.
+    separator: Vec<Piece>,
+    case: Case,
+}
+
+/// The case of the words written to a slot in a [`Format`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// All lowercase, as in `digital`, for a slot like `x`.
+    Lower,
+    /// All uppercase, as in `DIGITAL`, for a slot like `X`.
+    Upper,
+    /// The first letter in uppercase, as in `Digital`, for a slot like `Xx`.
+    Capitalized,
+}
+
+/// Part of a separator.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Piece {
+    Text(String),
+    /// A word number, from the `%n` directive.
+    Number,
+}
+
+impl Format {
+    /// Parse a template.
+    ///
+    /// Returns [`Error::InvalidFormat`] if the template has no word slots, a slot with mixed
+    /// case, or an unknown directive.
+    pub fn new(template: &str) -> Result<Format> {
+        let invalid = |reason| Error::InvalidFormat { template: template.to_string(), reason };
+        let mut slots = Vec::new();
+        let mut line_groups = 0;
+        let mut trailing = false;
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+
+        let mut rest = template;
+        while let Some(c) = rest.chars().next() {
+            if c == '%' {
+                let directive = &rest[1..];
+                let digits = directive.find(|c: char| !c.is_ascii_digit()).unwrap_or(directive.len());
+                let (end, piece) = match directive.as_bytes().get(digits) {
+                    Some(b'n') if digits == 0 => (1, Some(Piece::Number)),
+                    Some(b'%') if digits == 0 => (1, Some(Piece::Text("%".to_string()))),
+                    Some(b't') if digits == 0 => {
+                        trailing = true;
+                        (1, None)
+                    }
+                    Some(b'l') if digits > 0 => {
+                        line_groups = directive[..digits].parse().unwrap_or(0);
+                        if line_groups == 0 {
+                            return Err(invalid("line break interval must be a positive number"))
+                        }
+                        (digits + 1, None)
+                    }
+                    Some(_) => return Err(invalid("unknown directive")),
+                    None => return Err(invalid("incomplete directive")),
+                };
+                if let Some(piece) = piece {
+                    push_text(&mut pieces, &mut text);
+                    pieces.push(piece);
+                }
+                rest = &directive[end..];
+            } else if c.is_ascii_alphabetic() {
+                let end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+                let case = Case::of_slot(&rest.as_bytes()[..end]).ok_or_else(|| {
+                    invalid("word slot must be lowercase, uppercase, or capitalized")
+                })?;
+                push_text(&mut pieces, &mut text);
+                slots.push(Slot { separator: mem::take(&mut pieces), case });
+                rest = &rest[end..];
+            } else {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if slots.is_empty() {
+            return Err(invalid("template has no word slots"))
+        }
+        push_text(&mut pieces, &mut text);
+        Ok(Format { template: template.to_string(), slots, tail: pieces, line_groups, trailing })
+    }
+
+    /// Parse a template given as bytes, which must be valid UTF-8.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn from_bytes(template: &[u8]) -> Result<Format> {
+        match str::from_utf8(template) {
+            Ok(template) => Format::new(template),
+            Err(_) => Err(Error::InvalidFormat {
+                template: String::from_utf8_lossy(template).into_owned(),
+                reason: "template is not valid UTF-8",
+            }),
+        }
+    }
+
+    /// The template this format was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// The number of word slots in one repetition of the template.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The greatest length in bytes of the string encoding `len` bytes in this format.
+    ///
+    /// This assumes that every word is as long as the longest word in the list.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let format = mnemonic::Format::new("%n. x\n").unwrap();
+    /// assert_eq!(format.max_encoded_len(8), 6 * 10 + 5);
+    /// ```
+    pub fn max_encoded_len(&self, len: usize) -> usize {
+        let words = words_required(len);
+        let mut finish_len = 0;
+        let _ = self.finish(words, &mut |s: &[u8]| {
+            finish_len += s.len();
+            Ok::<_, Infallible>(())
+        });
+        let mut total = words.saturating_mul(MN_MAX_WORD_LEN).saturating_add(finish_len);
+        if words == 0 {
+            return total
+        }
+
+        // Word `n` is preceded by the separator of slot `n % slots`, numbered `n + 1`.
+        let slots = self.slots.len();
+        for (i, slot) in self.slots.iter().enumerate() {
+            let residue = (i + 1) % slots;
+            total = total.saturating_add(pieces_len(&slot.separator,
+                                                    count_congruent(words, slots, residue),
+                                                    count_digits_congruent(words, slots, residue)));
+        }
+        // Every later group is preceded by a line break or the tail, numbered with the number of
+        // words before it.
+        let groups = count_congruent(words - 1, slots, 0);
+        let group_digits = count_digits_congruent(words - 1, slots, 0);
+        let (breaks, break_digits) = match self.line_groups {
+            0 => (0, 0),
+            line_groups => {
+                let period = slots.saturating_mul(line_groups);
+                let breaks = count_congruent(words - 1, period, 0);
+                (breaks, count_digits_congruent(words - 1, period, 0))
+            }
+        };
+        total.saturating_add(breaks)
+             .saturating_add(pieces_len(&self.tail, groups - breaks, group_digits - break_digits))
+    }
+
+    /// Encode the bytes of `src` in this format, and write the string to `dest`.
+    #[cfg(feature = "std")]
+    pub fn encode<S, W>(&self, src: S, mut dest: W) -> io::Result<()>
+        where S: AsRef<[u8]>,
+              W: Write
+    {
+        mn_encode(src.as_ref(), self, |s| dest.write_all(s))
+    }
+
+    /// Encode the bytes of `src` in this format, and write the string to a [`fmt::Write`].
+    pub fn encode_to_fmt<S, W>(&self, src: S, mut dest: W) -> fmt::Result
+        where S: AsRef<[u8]>,
+              W: fmt::Write
+    {
+        // Templates are split only at ASCII characters, so each piece is valid UTF-8.
+        mn_encode(src.as_ref(), self, |s| dest.write_str(str::from_utf8(s).unwrap()))
+    }
+
+    /// Decode a string in this format into bytes, and write the bytes to `dest`.
+    ///
+    /// Unlike [`decode`](crate::decode), which accepts anything but letters between words, this
+    /// requires `src` to be exactly what [`encode`](Format::encode) would write: every separator
+    /// must match the template, and every word must be in the case of its slot.  If a group of
+    /// words is missing a word or has an extra one, the error says which group.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use mnemonic::{Error, Format};
+    ///
+    /// let format = Format::default();
+    /// let mut dest = Vec::<u8>::new();
+    /// format.decode("digital-apollo-aroma--rival-artist-rebel", &mut dest).unwrap();
+    /// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+    ///
+    /// let e = format.decode("digital-apollo--rival-artist-rebel", Vec::new()).unwrap_err();
+    /// assert!(matches!(e, Error::WrongGroupSize { group: 0, words: 2, expected: 3, .. }));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn decode<S, W>(&self, src: S, mut dest: W) -> Result<usize>
+        where S: AsRef<[u8]>,
+              W: Write
+    {
+        self.mn_decode(src.as_ref(), |bytes| Ok(dest.write_all(bytes)?))
+    }
+
+    /// Decode a string in this format and return the bytes as a `Vec`.
+    ///
+    /// See [`decode`](Format::decode).  Unlike that method, this is available without the `std`
+    /// feature.
+    pub fn decode_to_vec<S: AsRef<[u8]>>(&self, src: S) -> Result<Vec<u8>> {
+        let mut dest = Vec::new();
+        self.mn_decode(src.as_ref(), |bytes| {
+            dest.extend_from_slice(bytes);
+            Ok(())
+        })?;
+        Ok(dest)
+    }
+
+    /// Decode `src`, checking it against this format, and pass each decoded chunk of bytes to
+    /// `emit`.
+    fn mn_decode<F>(&self, src: &[u8], emit: F) -> Result<usize>
+        where F: FnMut(&[u8]) -> Result<()>
+    {
+        self.mn_decode_with(src, MN_BASE, true, false, mn_resolve_exact, emit)
+    }
+
+    /// Decode `src`, checking it against this format, and pass each decoded chunk of bytes to
+    /// `emit`.
+    ///
+    /// Each word is checked against the case of its slot if `match_case` is true, and then
+    /// converted to lowercase and passed to `resolve` to find its index in a word list with the
+    /// given `base`.  If `allow_trailing` is true, decoding ends before the first unrecognized
+    /// word, and the rest of `src` is ignored.
+    pub(crate) fn mn_decode_with<R, F>(&self, src: &[u8], base: u32, match_case: bool,
+                                       allow_trailing: bool, mut resolve: R, emit: F)
+        -> Result<usize>
+        where R: FnMut(&[u8], &Position) -> Result<u32>,
+              F: FnMut(&[u8]) -> Result<()>
+    {
+        let words: Vec<(usize, &[u8])> = mn_words(src).collect();
+        let mut end = 0; // End of the previous word.
+        let mut stopped = false;
+        let indices = words.iter().enumerate().map_while(|(n, &(start, word))| {
+            let position = Position { word: n, bytes: Some(start..start + word.len()) };
+            let case = self.slots[n % self.slots.len()].case;
+            let i = match match_case && Case::of_slot(word) != Some(case) {
+                true => Err(WordError::UnrecognizedWord.at(word, position.clone())),
+                false => resolve(&word.to_ascii_lowercase(), &position),
+            };
+            let i = match i {
+                Err(Error::UnrecognizedWord { .. }) if allow_trailing => {
+                    stopped = true;
+                    return None
+                }
+                i => i,
+            };
+            let checked = i.and_then(|i| {
+                self.check_separator(src, &words, n, end..start)?;
+                Ok((i, word, position))
+            });
+            end = start + word.len();
+            Some(checked)
+        });
+        let len = mn_decode_indices(indices, base, emit)?;
+
+        if !stopped {
+            let n = words.len();
+            let mut expected = Vec::new();
+            let _ = self.finish(n, &mut push_to(&mut expected));
+            if src[end..] != expected[..] {
+                return Err(unexpected_separator(&src[end..], &expected, n, end..src.len()))
+            }
+        }
+        Ok(len)
+    }
+
+    /// Check the separator at `range`, before word number `n` of `words`.
+    fn check_separator(&self, src: &[u8], words: &[(usize, &[u8])], n: usize, range: Range<usize>)
+        -> Result<()>
+    {
+        let found = &src[range.clone()];
+        let expected = self.separator(n);
+        if found == expected {
+            return Ok(())
+        }
+
+        // Look for a group that ended early, or went on too long.
+        let slots = self.slots.len();
+        let group_position = |group: usize| {
+            let (start, word) = words[group * slots];
+            Position { word: group * slots, bytes: Some(start..start + word.len()) }
+        };
+        if n % slots != 0 && self.group_separators(n).contains(&found.to_vec()) {
+            let group = n / slots;
+            return Err(Error::WrongGroupSize {
+                group, words: n % slots, expected: slots, position: group_position(group),
+            })
+        }
+        if n % slots == 0 && n > 0 && self.word_separators(n).contains(&found.to_vec()) {
+            let group = n / slots - 1;
+            let extra = (n + 1..words.len()).take_while(|&m| {
+                let (prev, word) = words[m - 1];
+                let separator = &src[prev + word.len()..words[m].0];
+                !self.group_separators(m).contains(&separator.to_vec())
+            }).count() + 1;
+            return Err(Error::WrongGroupSize {
+                group, words: slots + extra, expected: slots, position: group_position(group),
+            })
+        }
+        Err(unexpected_separator(found, &expected, n, range))
+    }
+
+    /// The separators that could start a new group at word number `n`.
+    fn group_separators(&self, n: usize) -> Vec<Vec<u8>> {
+        let mut separators = Vec::new();
+        let mut tail = Vec::new();
+        let _ = write_pieces(&self.tail, n, &mut push_to(&mut tail));
+        separators.push(tail);
+        if self.line_groups > 0 {
+            separators.push(b"\n".to_vec());
+        }
+        for separator in &mut separators {
+            let _ = write_pieces(&self.slots[0].separator, n + 1, &mut push_to(separator));
+        }
+        separators
+    }
+
+    /// The separators that could come before word number `n` within a group.
+    fn word_separators(&self, n: usize) -> Vec<Vec<u8>> {
+        self.slots[1..].iter().map(|slot| {
+            let mut separator = Vec::new();
+            let _ = write_pieces(&slot.separator, n + 1, &mut push_to(&mut separator));
+            separator
+        }).collect()
+    }
+
+    /// Pass the separators before word number `n` (counting from 0) to `emit`, followed by
+    /// `word`.
+    pub(crate) fn write_word<E, F>(&self, n: usize, word: &[u8], emit: &mut F) -> result::Result<(), E>
+        where F: FnMut(&[u8]) -> result::Result<(), E>
+    {
+        self.write_separator(n, emit)?;
+        self.slots[n % self.slots.len()].case.write(word, emit)
+    }
+
+    /// Pass the separators before word number `n` (counting from 0) to `emit`.
+    pub(crate) fn write_separator<E, F>(&self, n: usize, emit: &mut F) -> result::Result<(), E>
+        where F: FnMut(&[u8]) -> result::Result<(), E>
+    {
+        let slot = n % self.slots.len();
+        if slot == 0 && n > 0 {
+            let groups = n / self.slots.len();
+            if self.line_groups > 0 && groups % self.line_groups == 0 {
+                emit(b"\n")?;
+            } else {
+                write_pieces(&self.tail, n, emit)?;
+            }
+        }
+        write_pieces(&self.slots[slot].separator, n + 1, emit)
+    }
+
+    /// The separators before word number `n` (counting from 0).
+    pub(crate) fn separator(&self, n: usize) -> Vec<u8> {
+        let mut separator = Vec::new();
+        let _ = self.write_separator(n, &mut push_to(&mut separator));
+        separator
+    }
+
+    /// The case of word number `n`.
+    pub(crate) fn case(&self, n: usize) -> Case {
+        self.slots[n % self.slots.len()].case
+    }
+
+    /// Write every word in `case`.
+    pub(crate) fn set_case(&mut self, case: Case) {
+        for slot in &mut self.slots {
+            slot.case = case;
+        }
+        self.template = self.to_template();
+    }
+
+    /// Put `separator` between the words in each repetition of the template.
+    ///
+    /// Returns [`Error::InvalidFormat`] if `separator` is empty or contains ASCII letters, and
+    /// leaves the format unchanged.
+    pub(crate) fn set_separator(&mut self, separator: &str) -> Result<()> {
+        if separator.is_empty() {
+            return Err(Error::InvalidFormat {
+                template: String::new(),
+                reason: "separator must not be empty",
+            })
+        }
+        let pieces = text_pieces(separator)?;
+        for slot in &mut self.slots[1..] {
+            slot.separator = pieces.clone();
+        }
+        self.template = self.to_template();
+        Ok(())
+    }
+
+    /// Put `separator` between repetitions of the template.
+    ///
+    /// Returns [`Error::InvalidFormat`] if `separator` contains ASCII letters, and leaves the
+    /// format unchanged.
+    pub(crate) fn set_group_separator(&mut self, separator: &str) -> Result<()> {
+        self.tail = text_pieces(separator)?;
+        self.template = self.to_template();
+        Ok(())
+    }
+
+    /// A template equivalent to this format.
+    fn to_template(&self) -> String {
+        let mut template = String::new();
+        for slot in &self.slots {
+            push_template(&mut template, &slot.separator);
+            template.push_str(match slot.case {
+                Case::Lower => "x",
+                Case::Upper => "X",
+                Case::Capitalized => "Xx",
+            });
+        }
+        push_template(&mut template, &self.tail);
+        if self.trailing {
+            template.push_str("%t");
+        }
+        if self.line_groups > 0 {
+            let _ = write!(template, "%{}l", self.line_groups);
+        }
+        template
+    }
+
+    /// Pass the separator after the last word to `emit`, if the format has one, given the
+    /// number of words `n` that were written.
+    pub(crate) fn finish<E, F>(&self, n: usize, emit: &mut F) -> result::Result<(), E>
+        where F: FnMut(&[u8]) -> result::Result<(), E>
+    {
+        if !self.trailing || n == 0 {
+            return Ok(())
+        }
+        match n % self.slots.len() {
+            0 => write_pieces(&self.tail, n, emit),
+            slot => write_pieces(&self.slots[slot].separator, n + 1, emit),
+        }
+    }
+}
+
+impl Case {
+    /// The case of a word slot, or `None` if it has mixed case.
+    fn of_slot(slot: &[u8]) -> Option<Case> {
+        let rest_lower = slot[1..].iter().all(|c| c.is_ascii_lowercase());
+        if slot.iter().all(|c| c.is_ascii_lowercase()) {
+            Some(Case::Lower)
+        } else if slot.iter().all(|c| c.is_ascii_uppercase()) {
+            Some(Case::Upper)
+        } else if rest_lower {
+            Some(Case::Capitalized)
+        } else {
+            None
+        }
+    }
+
+    /// Pass `word` to `emit` in this case.
+    pub(crate) fn write<E, F>(self, word: &[u8], emit: &mut F) -> result::Result<(), E>
+        where F: FnMut(&[u8]) -> result::Result<(), E>
+    {
+        let mut buf = [0; 16];
+        for (i, chunk) in word.chunks(buf.len()).enumerate() {
+            for (j, (b, &c)) in buf.iter_mut().zip(chunk).enumerate() {
+                *b = match self {
+                    Case::Upper => c.to_ascii_uppercase(),
+                    Case::Capitalized if i == 0 && j == 0 => c.to_ascii_uppercase(),
+                    _ => c.to_ascii_lowercase(),
+                };
+            }
+            emit(&buf[..chunk.len()])?;
+        }
+        Ok(())
+    }
+}
+
+/// Move any `text` to the end of `pieces`.
+fn push_text(pieces: &mut Vec<Piece>, text: &mut String) {
+    if !text.is_empty() {
+        pieces.push(Piece::Text(mem::take(text)));
+    }
+}
+
+/// A function for `emit` arguments that appends to `out`.
+fn push_to(out: &mut Vec<u8>) -> impl FnMut(&[u8]) -> result::Result<(), Infallible> + '_ {
+    |s| {
+        out.extend_from_slice(s);
+        Ok(())
+    }
+}
+
+/// An [`Error::UnexpectedSeparator`] for the separator at `range`, before word number `n`.
+fn unexpected_separator(found: &[u8], expected: &[u8], n: usize, range: Range<usize>) -> Error {
+    Error::UnexpectedSeparator {
+        separator: String::from_utf8_lossy(found).into_owned(),
+        expected: String::from_utf8_lossy(expected).into_owned(),
+        position: Position { word: n, bytes: Some(range) },
+    }
+}
+
+/// The pieces of a separator with the literal text `separator`.
+fn text_pieces(separator: &str) -> Result<Vec<Piece>> {
+    if separator.bytes().any(|c| c.is_ascii_alphabetic()) {
+        // Letters would be read as word slots if the separator were part of a template.
+        return Err(Error::InvalidFormat {
+            template: separator.to_string(),
+            reason: "separator must not contain letters",
+        })
+    }
+    Ok(match separator {
+        "" => Vec::new(),
+        _ => vec![Piece::Text(separator.to_string())],
+    })
+}
+
+/// Append the template syntax for `pieces` to `template`.
+fn push_template(template: &mut String, pieces: &[Piece]) {
+    for piece in pieces {
+        match piece {
+            Piece::Text(text) => template.push_str(&text.replace('%', "%%")),
+            Piece::Number => template.push_str("%n"),
+        }
+    }
+}
+
+/// The total length of `count` copies of `pieces`, whose word numbers have `digits` digits in
+/// all.
+fn pieces_len(pieces: &[Piece], count: usize, digits: usize) -> usize {
+    pieces.iter().fold(0, |total: usize, piece| {
+        total.saturating_add(match piece {
+            Piece::Text(text) => text.len().saturating_mul(count),
+            Piece::Number => digits,
+        })
+    })
+}
+
+/// The number of integers in `1..=end` that are congruent to `residue` modulo `period`.
+fn count_congruent(end: usize, period: usize, residue: usize) -> usize {
+    let up_to = |x: usize| if x < residue { 0 } else { (x - residue) / period + 1 };
+    up_to(end) - up_to(0)
+}
+
+/// The total number of decimal digits in the integers in `1..=end` that are congruent to
+/// `residue` modulo `period`.
+fn count_digits_congruent(end: usize, period: usize, residue: usize) -> usize {
+    let mut total = 0usize;
+    let mut start = 1usize;
+    let mut digits = 1;
+    while start <= end {
+        // All integers in `start..=last` have the same number of digits.
+        let last = start.checked_mul(10).map_or(end, |next| end.min(next - 1));
+        let count = count_congruent(last, period, residue)
+                    - count_congruent(start - 1, period, residue);
+        total = total.saturating_add(count.saturating_mul(digits));
+        start = match last.checked_add(1) {
+            Some(next) => next,
+            None => break,
+        };
+        digits += 1;
+    }
+    total
+}
+
+/// Pass a separator to `emit`, numbering words with `number`.
+fn write_pieces<E, F>(pieces: &[Piece], number: usize, emit: &mut F) -> result::Result<(), E>
+    where F: FnMut(&[u8]) -> result::Result<(), E>
+{
+    for piece in pieces {
+        match piece {
+            Piece::Text(text) => emit(text.as_bytes())?,
+            Piece::Number => {
+                let mut buf = [0; 20];
+                let mut i = buf.len();
+                let mut number = number;
+                loop {
+                    i -= 1;
+                    buf[i] = b'0' + (number % 10) as u8;
+                    number /= 10;
+                    if number == 0 {
+                        break
+                    }
+                }
+                emit(&buf[i..])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Default for Format {
+    /// The default format, [`MN_FDEFAULT`](crate::MN_FDEFAULT).
+    fn default() -> Self {
+        Format::new(MN_FDEFAULT_STR).unwrap()
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(template: &str) -> Result<Format> {
+        Format::new(template)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+
+    fn encode(template: &str, src: &[u8]) -> String {
+        let mut s = String::new();
+        Format::new(template).unwrap().encode_to_fmt(src, &mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_parse() {
+        let format = Format::new("x-x-x--").unwrap();
+        assert_eq!(format.slots(), 3);
+        assert_eq!(format, Format::default());
+        assert_eq!(format.to_string(), "x-x-x--");
+        assert_eq!(Format::new("<word> ").unwrap().slots(), 1);
+        assert_eq!(Format::new("%n.%% X %12l%t").unwrap().slots(), 1);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for template in ["", " ", "--\n", "1 2 3", "%n", "xX", "x %q", "x %", "x %0l", "x %3"] {
+            assert!(matches!(Format::new(template), Err(Error::InvalidFormat { .. })), "{}", template);
+        }
+        let e = Format::from_bytes(b"x\xFF").unwrap_err();
+        assert_eq!(e.to_string(), "invalid format 'x\u{FFFD}': template is not valid UTF-8");
+        let e = Format::new("x %q").unwrap_err();
+        assert_eq!(e.to_string(), "invalid format 'x %q': unknown directive");
+    }
+
+    #[test]
+    fn test_encode() {
+        let src = [101, 2, 240, 6, 108, 11, 20, 97];
+        assert_eq!(encode("x x\n", &src), "digital apollo\naroma rival\nartist rebel");
+        assert_eq!(encode("(x)", &src[..4]), "(digital)(apollo)(aroma");
+        assert_eq!(encode("x", &src[..4]), "digitalapolloaroma");
+    }
+
+    #[test]
+    fn test_directives() {
+        let src = [101, 2, 240, 6, 108, 11, 20, 97];
+        assert_eq!(encode("X-Xx-x", &src[..4]), "DIGITAL-Apollo-aroma");
+        assert_eq!(encode("%n. Xx ", &src[..4]), "1. Digital 2. Apollo 3. Aroma");
+        assert_eq!(encode("Xx (%n) ", &src[..4]), "Digital (1) Apollo (2) Aroma");
+        assert_eq!(encode("(x)%t", &src[..4]), "(digital)(apollo)(aroma)");
+        assert_eq!(encode("x-x-x--%t", &src[..2]), "radio-academy-");
+        assert_eq!(encode("x x--%2l", &src), "digital apollo--aroma rival\nartist rebel");
+        assert_eq!(encode("%%x", &src[..2]), "%radio%academy");
+    }
+
+    #[test]
+    fn test_max_encoded_len() {
+        for template in ["x-x-x--", "x x\n", "%n. Xx %2l", "(x)%t", "x, %3l%t", "%n-x%n %2l%t"] {
+            let format = Format::new(template).unwrap();
+            for len in (0..40).chain([150, 1500]) {
+                let mut s = String::new();
+                format.encode_to_fmt(vec![0x55; len], &mut s).unwrap();
+                let words = crate::words_required(len);
+                let letters = s.bytes().filter(u8::is_ascii_alphabetic).count();
+                let longest = s.len() - letters + words * MN_MAX_WORD_LEN;
+                assert_eq!(format.max_encoded_len(len), longest, "{} {}", template, len);
+            }
+        }
+        assert_eq!(Format::default().max_encoded_len(usize::MAX), usize::MAX);
+        assert_eq!(Format::new("%n. x\n").unwrap().max_encoded_len(usize::MAX), usize::MAX);
+        assert_eq!(count_digits_congruent(1000, 1, 0), 9 + 90 * 2 + 900 * 3 + 4);
+        assert_eq!(count_digits_congruent(1000, 3, 1), 3 + 30 * 2 + 300 * 3 + 4);
+        let digits = usize::MAX.to_string().len();
+        assert_eq!(count_digits_congruent(usize::MAX, usize::MAX, 0), digits);
+    }
+
+    #[test]
+    fn test_decode_strict() {
+        let src = [101, 2, 240, 6, 108, 11, 20, 97];
+        for template in ["x-x-x--", "x x\n", "%n. Xx %2l", "(x)%t", "X-Xx-x %t"] {
+            let format = Format::new(template).unwrap();
+            for len in 0..=src.len() {
+                assert_eq!(format.decode_to_vec(encode(template, &src[..len])).unwrap(), &src[..len]);
+            }
+        }
+
+        let format = Format::default();
+        let e = format.decode_to_vec("digital!!apollo").unwrap_err();
+        assert_eq!(e.to_string(), "unexpected separator '!!' before word 2 (bytes 7..9): expected '-'");
+        let e = format.decode_to_vec(" digital").unwrap_err();
+        assert_eq!(e.to_string(), "unexpected separator ' ' before word 1 (bytes 0..1): expected ''");
+        let e = format.decode_to_vec("digital-apollo-").unwrap_err();
+        assert_eq!(e.to_string(), "unexpected separator '-' before word 3 (bytes 14..15): expected ''");
+        let e = format.decode_to_vec("digital-Apollo").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'Apollo' at word 2 (bytes 8..14)");
+
+        let e = format.decode_to_vec("digital-apollo-aroma--rival-artist--rebel-cobra-elite")
+                      .unwrap_err();
+        assert_eq!(e.to_string(),
+                   "expected 3 words in group 2 starting at word 4 (bytes 22..27), found 2");
+        let e = format.decode_to_vec("digital-apollo-aroma-rival--artist-rebel-cobra").unwrap_err();
+        assert_eq!(e.to_string(),
+                   "expected 3 words in group 1 starting at word 1 (bytes 0..7), found 4");
+
+        let format = Format::new("x x\n").unwrap();
+        let e = format.decode_to_vec("digital apollo\naroma rival artist\nrebel").unwrap_err();
+        assert!(matches!(e, Error::WrongGroupSize { group: 1, words: 3, expected: 2, .. }));
+        let e = format.decode_to_vec("digital apollo\naroma rival  artist").unwrap_err();
+        assert_eq!(e.to_string(),
+                   "unexpected separator '  ' before word 5 (bytes 26..28): expected '\\n'");
+    }
+
+    quickcheck! {
+        fn quickcheck_decode_strict(src: Vec<u8>) -> bool {
+            ["x-x-x--", "%n. Xx %2l", "X, %t"].iter().all(|template| {
+                let format = Format::new(template).unwrap();
+                let mut s = String::new();
+                format.encode_to_fmt(&src, &mut s).unwrap();
+                format.decode_to_vec(s).unwrap() == src
+            })
+        }
+    }
+}
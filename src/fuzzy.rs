@@ -0,0 +1,178 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::error::WordError;
+#[cfg(feature = "std")]
+use crate::{mn_decode, DefaultWordlist, MN_BASE};
+use crate::{Error, Position, Result, Wordlist};
+
+/// A substitution made by a decoder for a word that did not exactly match the word list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Correction {
+    /// The word as it appeared in the input.
+    pub original: String,
+    /// The word from the word list that replaced it.
+    pub corrected: String,
+    /// The position of the original word in the input.
+    pub position: Position,
+}
+
+impl Correction {
+    pub(crate) fn new(original: &[u8], corrected: &[u8], position: &Position) -> Self {
+        Correction {
+            original: String::from_utf8_lossy(original).into_owned(),
+            corrected: String::from_utf8_lossy(corrected).into_owned(),
+            position: position.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Correction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "corrected '{}' to '{}' at {}", self.original, self.corrected, self.position)
+    }
+}
+
+/// Decode the mnemonic string `src` into bytes, correcting misspelled words, and write the
+/// bytes to `dest`.
+///
+/// Each word that is not in the word list is replaced by the closest word in the list, as long
+/// as it is at most `max_distance` edits away.  An edit is the insertion, deletion, or
+/// substitution of a single letter, or the transposition of two adjacent letters.  A word that
+/// is equally close to more than one word in the list is rejected with
+/// [`Error::AmbiguousWord`].
+///
+/// Returns the corrections that were made.
+///
+/// ## Example
+///
+/// ```
+/// let src = "digital-aplolo-aroma--rival-artist-rebel";
+///
+/// let mut dest = Vec::<u8>::new();
+/// let corrections = mnemonic::decode_fuzzy(src, 2, &mut dest).unwrap();
+///
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// assert_eq!(corrections[0].original, "aplolo");
+/// assert_eq!(corrections[0].corrected, "apollo");
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_fuzzy<S, W>(src: S, max_distance: usize, mut dest: W) -> Result<Vec<Correction>>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    let mut corrections = Vec::new();
+    mn_decode(src.as_ref(), MN_BASE,
+              |word, position| {
+                  mn_resolve_fuzzy(&DefaultWordlist, word, position, max_distance,
+                                   &mut corrections)
+              },
+              |bytes| Ok(dest.write_all(bytes)?))?;
+    Ok(corrections)
+}
+
+/// Find the index of `word` in `wordlist`, or of the closest word at most `max_distance` edits
+/// away.  Any correction is appended to `corrections`.
+pub(crate) fn mn_resolve_fuzzy<L>(wordlist: &L, word: &[u8], position: &Position,
+                                  max_distance: usize, corrections: &mut Vec<Correction>)
+    -> Result<u32>
+    where L: Wordlist + ?Sized
+{
+    if let Some(i) = wordlist.index(word) {
+        return Ok(i)
+    }
+    let mut best = max_distance;
+    let mut candidates = Vec::new();
+    for i in 0..wordlist.base() + wordlist.remainder() {
+        let candidate = wordlist.word(i);
+        if word.len().abs_diff(candidate.len()) > best {
+            continue
+        }
+        let distance = mn_edit_distance(word, candidate);
+        if distance < best || (distance == best && candidates.is_empty()) {
+            best = distance;
+            candidates.clear();
+            candidates.push(i);
+        } else if distance == best {
+            candidates.push(i);
+        }
+    }
+    match candidates[..] {
+        [] => Err(WordError::UnrecognizedWord.at(word, position.clone())),
+        [i] => {
+            corrections.push(Correction::new(word, wordlist.word(i), position));
+            Ok(i)
+        }
+        _ => Err(Error::ambiguous(word, position, candidates.iter().map(|&i| wordlist.word(i)))),
+    }
+}
+
+/// The optimal string alignment distance between `a` and `b`: the number of insertions,
+/// deletions, substitutions, and adjacent transpositions needed to turn one into the other.
+pub(crate) fn mn_edit_distance(a: &[u8], b: &[u8]) -> usize {
+    // Three rows of the dynamic programming table: `prev2` and `prev` for the previous two
+    // bytes of `a`, and `cur` for the current one.
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = (a[i - 1] != b[j - 1]) as usize;
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        core::mem::swap(&mut prev2, &mut prev);
+        core::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultWordlist, MN_WORDS};
+
+    fn resolve(word: &str, max_distance: usize) -> Result<(u32, Vec<Correction>)> {
+        let mut corrections = Vec::new();
+        let i = mn_resolve_fuzzy(&DefaultWordlist, word.as_bytes(), &Position::default(),
+                                 max_distance, &mut corrections)?;
+        Ok((i, corrections))
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(mn_edit_distance(b"apollo", b"apollo"), 0);
+        assert_eq!(mn_edit_distance(b"aplolo", b"apollo"), 1);
+        assert_eq!(mn_edit_distance(b"apolo", b"apollo"), 1);
+        assert_eq!(mn_edit_distance(b"", b"fax"), 3);
+        assert_eq!(mn_edit_distance(b"kitten", b"sitting"), 3);
+    }
+
+    #[test]
+    fn test_resolve_fuzzy() {
+        let (i, corrections) = resolve("digital", 2).unwrap();
+        assert_eq!(MN_WORDS[i as usize], b"digital");
+        assert!(corrections.is_empty());
+
+        let (i, corrections) = resolve("digitel", 2).unwrap();
+        assert_eq!(MN_WORDS[i as usize], b"digital");
+        assert_eq!(corrections[0].to_string(), "corrected 'digitel' to 'digital' at word 1");
+    }
+
+    #[test]
+    fn test_resolve_fuzzy_errors() {
+        assert!(matches!(resolve("digitel", 0), Err(Error::UnrecognizedWord { .. })));
+        assert!(matches!(resolve("qqqqqqqq", 2), Err(Error::UnrecognizedWord { .. })));
+        match resolve("rodio", 1) {
+            Err(Error::AmbiguousWord { candidates, .. }) => assert_eq!(candidates, ["radio", "rodeo"]),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
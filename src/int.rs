@@ -0,0 +1,94 @@
+use alloc::string::String;
+
+use crate::{decode_to_array, mn_words, to_string, words_required, Error, Result};
+
+macro_rules! int_functions {
+    ($($t:ident, $encode:ident, $decode:ident;)*) => {$(
+        #[doc = concat!("Encode a `", stringify!($t), "` as a mnemonic string.")]
+        ///
+        /// The number is encoded from its little-endian bytes, so the string always has the
+        /// same number of words, and the first word holds the lowest digits.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        #[doc = concat!("let s = mnemonic::", stringify!($encode), "(12345);")]
+        #[doc = concat!("assert_eq!(mnemonic::", stringify!($decode), "(&s).unwrap(), 12345);")]
+        /// ```
+        pub fn $encode(n: $t) -> String {
+            to_string(n.to_le_bytes())
+        }
+
+        #[doc = concat!("Decode a `", stringify!($t), "` from a mnemonic string written by [`",
+                        stringify!($encode), "`].")]
+        ///
+        /// Fails with [`Error::WrongWordCount`] if `src` does not have exactly as many words as
+        #[doc = concat!("[`", stringify!($encode), "`] writes.")]
+        pub fn $decode<S: AsRef<[u8]>>(src: S) -> Result<$t> {
+            decode_int(src.as_ref()).map($t::from_le_bytes)
+        }
+    )*}
+}
+
+int_functions! {
+    u16, encode_u16, decode_u16;
+    u32, encode_u32, decode_u32;
+    u64, encode_u64, decode_u64;
+    u128, encode_u128, decode_u128;
+}
+
+/// Decode exactly `N` bytes from `src`, which must have exactly the number of words that
+/// encode `N` bytes.
+fn decode_int<const N: usize>(src: &[u8]) -> Result<[u8; N]> {
+    let expected = words_required(N);
+    let words = mn_words(src).count();
+    if words != expected {
+        return Err(Error::WrongWordCount { expected, words })
+    }
+    decode_to_array(src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode_u16(0), "academy-academy");
+        assert_eq!(encode_u32(0x06F0_0265), "digital-apollo-aroma");
+        assert_eq!(encode_u32(0x06F0_0265), to_string([101, 2, 240, 6]));
+        assert_eq!(encode_u64(u64::MAX).split('-').filter(|w| !w.is_empty()).count(), 6);
+        assert_eq!(encode_u128(1).split('-').filter(|w| !w.is_empty()).count(), 12);
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode_u32("digital-apollo-aroma").unwrap(), 0x06F0_0265);
+        assert_eq!(decode_u16("academy-academy").unwrap(), 0);
+
+        let e = decode_u32("digital-apollo").unwrap_err();
+        assert_eq!(e.to_string(), "expected 3 words, found 2");
+        let e = decode_u16("digital-apollo-aroma").unwrap_err();
+        assert!(matches!(e, Error::WrongWordCount { expected: 2, words: 3 }));
+        let e = decode_u32(to_string([1, 2, 3])).unwrap_err();
+        assert!(matches!(e, Error::WrongLength { expected: 4, len: 3 }));
+        let e = decode_u16("digital-xyz").unwrap_err();
+        assert!(matches!(e, Error::UnrecognizedWord { .. }));
+    }
+
+    quickcheck! {
+        fn quickcheck_u32(n: u32) -> bool {
+            decode_u32(encode_u32(n)).unwrap() == n
+        }
+
+        fn quickcheck_u64(n: u64) -> bool {
+            decode_u64(encode_u64(n)).unwrap() == n
+        }
+
+        fn quickcheck_u128(n: u128) -> bool {
+            decode_u128(encode_u128(n)).unwrap() == n
+        }
+    }
+}
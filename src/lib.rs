@@ -14,6 +14,7 @@
 //! ## Example
 //!
 //! ```rust
+//! # #[cfg(feature = "std")] {
 //! let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
 //!
 //! let s = mnemonic::to_string(&bytes);
@@ -23,49 +24,92 @@
 //! mnemonic::decode(s, &mut decoded).unwrap();
 //!
 //! assert_eq!(decoded, [101, 2, 240, 6, 108, 11, 20, 97]);
+//! # }
 //! ```
+//!
+//! ## Features
+//!
+//! The `std` feature is enabled by default.  Without it, this crate is `no_std` (but still
+//! requires `alloc`), and the `io`-based APIs such as [`encode`] and [`decode`] are replaced by
+//! [`encode_to_fmt`], [`to_string`], and [`decode_to_vec`].
+//!
+//! The `unicode` feature, which is not enabled by default, lets `decode_normalized` read
+//! full-width and accented letters.
+//!
+//! The `serde` feature, which is not enabled by default, adds the [`serde`](mod@serde) module
+//! for serializing bytes and integers as mnemonic strings.
 
-use std::collections::HashMap;
-use std::error::Error as ErrorTrait;
-use std::fmt;
-use std::io;
-use std::io::prelude::*;
-use std::result;
-use std::sync::OnceLock;
-
-/// Errors returned by mnemonic decoding.
-#[derive(Debug)]
-pub enum Error {
-    Io(io::Error),
-    UnrecognizedWord,
-    UnexpectedRemainder,
-    UnexpectedRemainderWord,
-    DataPastRemainder,
-    InvalidEncoding,
-}
-use Error::*;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-/// Result type returned by mnemonic decoding.
-pub type Result<T> = result::Result<T, Error>;
+extern crate alloc;
 
-impl From<io::Error> for Error {
-    fn from(other: io::Error) -> Self { Io(other) }
-}
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{Range, RangeInclusive};
+use core::result;
+use core::str;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::io::prelude::*;
 
-impl ErrorTrait for Error {}
+#[cfg(feature = "std")]
+mod checksum;
+mod decoder;
+mod display;
+mod encoder;
+mod error;
+#[cfg(feature = "std")]
+mod fec;
+mod format;
+mod fuzzy;
+mod int;
+mod mnemonic;
+mod normalize;
+#[cfg(feature = "std")]
+mod phonetic;
+mod prefix;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod varint;
+mod wordlist;
+#[cfg(feature = "std")]
+mod writer;
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Io(ref e) => write!(f, "{}", e),
-            UnrecognizedWord => f.write_str("Unrecognized word"),
-            UnexpectedRemainder => f.write_str("Unexpected remainder (possible truncated string)"),
-            UnexpectedRemainderWord => f.write_str("Unexpected 24-bit remainder word"),
-            DataPastRemainder => f.write_str("Unexpected data past 24-bit remainder"),
-            InvalidEncoding => f.write_str("Invalid encoding"),
-        }
-    }
-}
+#[cfg(feature = "std")]
+pub use checksum::{decode_with_checksum, encode_with_checksum};
+pub use decoder::Decoder;
+pub use display::{display, Display};
+pub use encoder::Encoder;
+pub use error::{Error, Position, Result};
+#[cfg(feature = "std")]
+pub use fec::{decode_with_parity, encode_with_parity};
+pub use format::{Case, Format};
+use error::WordError;
+use Error::*;
+#[cfg(feature = "std")]
+pub use fuzzy::decode_fuzzy;
+pub use fuzzy::Correction;
+pub use int::{decode_u128, decode_u16, decode_u32, decode_u64};
+pub use int::{encode_u128, encode_u16, encode_u32, encode_u64};
+pub use mnemonic::Mnemonic;
+#[cfg(feature = "std")]
+pub use normalize::decode_normalized;
+#[cfg(feature = "std")]
+pub use phonetic::decode_phonetic;
+pub use prefix::unique_prefix;
+pub use varint::{decode_varint, encode_varint};
+pub use wordlist::{DefaultWordlist, Wordlist};
+#[cfg(feature = "std")]
+pub use prefix::{decode_prefix, encode_prefixes};
+#[cfg(feature = "std")]
+pub use reader::MnemonicReader;
+#[cfg(feature = "std")]
+pub use writer::MnemonicWriter;
 
 /// cubic root of 2^32, rounded up
 const MN_BASE: u32 = 1626;
@@ -74,9 +118,21 @@ const MN_BASE: u32 = 1626;
 const MN_REMAINDER: usize = 7;
 
 /// Default format for encoding
-pub const MN_FDEFAULT: &[u8] = b"x-x-x--";
+pub const MN_FDEFAULT: &[u8] = MN_FDEFAULT_STR.as_bytes();
+
+/// Default format for encoding, as a string
+const MN_FDEFAULT_STR: &str = "x-x-x--";
+
+/// Total number of words in the word list
+const MN_WORDS_LEN: usize = MN_BASE as usize + MN_REMAINDER;
+
+pub static MN_WORDS: [&[u8]; MN_WORDS_LEN] = *MN_WORD_LIST;
 
-pub static MN_WORDS: [&[u8]; MN_BASE as usize + MN_REMAINDER] = [
+/// Length of the longest word in the word list
+const MN_MAX_WORD_LEN: usize = 7;
+
+/// The word list, as a constant so that it can be sorted at compile time
+const MN_WORD_LIST: &[&[u8]; MN_WORDS_LEN] = &[
     b"academy",  b"acrobat",  b"active",   b"actor",    b"adam",     b"admiral",
     b"adrian",   b"africa",   b"agenda",   b"agent",    b"airline",  b"airport",
     b"aladdin",  b"alarm",    b"alaska",   b"albert",   b"albino",   b"album",
@@ -352,16 +408,64 @@ pub static MN_WORDS: [&[u8]; MN_BASE as usize + MN_REMAINDER] = [
     b"yes"
 ];
 
-fn mn_word_index() -> &'static HashMap<&'static [u8], u32> {
-    /// Map from words to indices in the MN_WORDS array
-    static MN_WORD_INDEX: OnceLock<HashMap<&'static [u8], u32>> = OnceLock::new();
-    MN_WORD_INDEX.get_or_init(|| {
-        let mut map = HashMap::new();
-        for (i, word) in MN_WORDS.iter().enumerate() {
-            map.insert(*word, i as u32);
+/// Indices into the MN_WORDS array, sorted by word
+static MN_WORDS_SORTED: [u16; MN_WORDS_LEN] = mn_sort_words();
+
+/// Return the index of `word` in the MN_WORDS array.
+fn mn_word_index(word: &[u8]) -> Option<u32> {
+    MN_WORDS_SORTED.binary_search_by(|&i| MN_WORDS[i as usize].cmp(word))
+                   .ok()
+                   .map(|pos| MN_WORDS_SORTED[pos] as u32)
+}
+
+/// Bottom-up merge sort of the word list, evaluated at compile time.
+const fn mn_sort_words() -> [u16; MN_WORDS_LEN] {
+    const fn less(a: &[u8], b: &[u8]) -> bool {
+        let mut i = 0;
+        while i < a.len() && i < b.len() {
+            if a[i] != b[i] {
+                return a[i] < b[i]
+            }
+            i += 1;
+        }
+        a.len() < b.len()
+    }
+    const fn min(a: usize, b: usize) -> usize {
+        if a < b { a } else { b }
+    }
+
+    let mut sorted = [0u16; MN_WORDS_LEN];
+    let mut i = 0;
+    while i < MN_WORDS_LEN {
+        sorted[i] = i as u16;
+        i += 1;
+    }
+    let mut width = 1;
+    while width < MN_WORDS_LEN {
+        let mut merged = [0u16; MN_WORDS_LEN];
+        let mut lo = 0;
+        while lo < MN_WORDS_LEN {
+            let mid = min(lo + width, MN_WORDS_LEN);
+            let hi = min(lo + 2 * width, MN_WORDS_LEN);
+            let (mut l, mut r, mut k) = (lo, mid, lo);
+            while k < hi {
+                let take_left = l < mid &&
+                    (r >= hi || !less(MN_WORD_LIST[sorted[r] as usize], MN_WORD_LIST[sorted[l] as usize]));
+                if take_left {
+                    merged[k] = sorted[l];
+                    l += 1;
+                } else {
+                    merged[k] = sorted[r];
+                    r += 1;
+                }
+                k += 1;
+            }
+            lo = hi;
         }
-        map
-    })
+        sorted = merged;
+        width *= 2;
+    }
+    sorted
 }
 
 /// Encode the bytes of `src` into a mnemonic string, and write the string to `dest`
@@ -374,6 +478,7 @@ fn mn_word_index() -> &'static HashMap<&'static [u8], u32> {
 /// mnemonic::encode(&bytes, &mut dest).unwrap();
 /// assert_eq!(dest, &b"digital-apollo-aroma--rival-artist-rebel"[..]);
 /// ```
+#[cfg(feature = "std")]
 pub fn encode<S, W>(src: S, dest: W) -> io::Result<()>
     where S: AsRef<[u8]>,
           W: Write
@@ -381,37 +486,79 @@ pub fn encode<S, W>(src: S, dest: W) -> io::Result<()>
     encode_with_format(src, MN_FDEFAULT, dest)
 }
 
-/// Encode the bytes of `s` with a custom template.
+/// Encode the bytes of `src` with a custom template.
+///
+/// See [`Format`] for a description of the template.  To encode many times with the same
+/// template, parse it once with [`Format::new`] and use [`Format::encode`] instead.
+///
+/// Returns an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput) wrapping
+/// [`Error::InvalidFormat`] if the template is invalid.
+///
+/// ## Example
+/// ```
+/// let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+/// let mut dest = Vec::<u8>::new();
+///
+/// mnemonic::encode_with_format(&bytes, "x x x\n", &mut dest).unwrap();
+/// assert_eq!(dest, &b"digital apollo aroma\nrival artist rebel"[..]);
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_with_format<S, F, W>(src: S, format: F, dest: W) -> io::Result<()>
+    where S: AsRef<[u8]>,
+          F: AsRef<[u8]>,
+          W: Write
+{
+    Format::from_bytes(format.as_ref())?.encode(src, dest)
+}
+
+/// Encode the bytes of `src` with a custom template and word list.
+///
+/// See [`Wordlist`] for an example.
 ///
-/// TODO: Document the template format.
-pub fn encode_with_format<S, F, W>(src: S, format: F, mut dest: W) -> io::Result<()>
+/// ## Panics
+///
+/// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+#[cfg(feature = "std")]
+pub fn encode_with_wordlist<S, F, L, W>(src: S, format: F, wordlist: &L, dest: W)
+    -> io::Result<()>
     where S: AsRef<[u8]>,
           F: AsRef<[u8]>,
+          L: Wordlist + ?Sized,
           W: Write
 {
-    let src = src.as_ref();
-    let format = format.as_ref();
+    let format = Format::from_bytes(format.as_ref())?;
+    Encoder::new().format(format).wordlist(wordlist).encode_to_writer(src, dest)
+}
 
-    let num_words = mn_words_required(src);
-    let mut n = 0;
-    let mut i = 0; // index within format
+/// Encode the bytes of `src` into a mnemonic string, and write the string to a
+/// [`fmt::Write`] such as a `String`.
+///
+/// Unlike [`encode`], this is available without the `std` feature.
+///
+/// ## Example
+/// ```
+/// let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+/// let mut dest = String::new();
+///
+/// mnemonic::encode_to_fmt(&bytes, &mut dest).unwrap();
+/// assert_eq!(dest, "digital-apollo-aroma--rival-artist-rebel");
+/// ```
+pub fn encode_to_fmt<S, W>(src: S, dest: W) -> fmt::Result
+    where S: AsRef<[u8]>,
+          W: fmt::Write
+{
+    encode_with_format_to_fmt(src, MN_FDEFAULT_STR, dest)
+}
 
-    while n < num_words {
-        while i < format.len() && !format[i].is_ascii_alphabetic() {
-            dest.write_all(&[format[i]])?;
-            i += 1;
-        }
-        if i == format.len() {
-            i = 0;
-            continue
-        }
-        while format[i].is_ascii_alphabetic() {
-            i += 1;
-        }
-        dest.write_all(mn_encode_word(src, n))?;
-        n += 1;
-    }
-    Ok(())
+/// Encode the bytes of `src` with a custom template, and write the string to a
+/// [`fmt::Write`].
+///
+/// See [`encode_with_format`].  Returns an error if the template is invalid.
+pub fn encode_with_format_to_fmt<S, W>(src: S, format: &str, dest: W) -> fmt::Result
+    where S: AsRef<[u8]>,
+          W: fmt::Write
+{
+    Format::new(format).map_err(|_| fmt::Error)?.encode_to_fmt(src, dest)
 }
 
 /// Encode the bytes of `src` and return the results as a String
@@ -424,18 +571,193 @@ pub fn encode_with_format<S, F, W>(src: S, format: F, mut dest: W) -> io::Result
 /// assert_eq!(s, "digital-apollo-aroma--rival-artist-rebel");
 /// ```
 pub fn to_string<S: AsRef<[u8]>>(src: S) -> String {
-    let mut v = Vec::new();
-    encode(src, &mut v).unwrap();
-    String::from_utf8(v).unwrap()
+    let mut s = String::new();
+    encode_to_fmt(src, &mut s).unwrap();
+    s
+}
+
+/// Return an iterator over the words that encode `src`, without any separators.
+///
+/// ## Example
+/// ```
+/// let words = mnemonic::words(&[101, 2, 240, 6]);
+/// assert_eq!(words.len(), 3);
+/// assert_eq!(words.collect::<Vec<_>>(), ["digital", "apollo", "aroma"]);
+/// ```
+pub fn words(src: &[u8]) -> Words<'_> {
+    Words { indices: bytes_to_indices(src) }
+}
+
+/// An iterator over the words that encode a byte slice, returned by [`words`].
+#[derive(Clone, Debug)]
+pub struct Words<'a> {
+    indices: Indices<'a>,
+}
+
+impl Iterator for Words<'_> {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<&'static str> {
+        self.indices.next().and_then(word_for_index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'static str> {
+        self.indices.nth(n).and_then(word_for_index)
+    }
+}
+
+impl DoubleEndedIterator for Words<'_> {
+    fn next_back(&mut self) -> Option<&'static str> {
+        self.indices.next_back().and_then(word_for_index)
+    }
+}
+
+impl ExactSizeIterator for Words<'_> {}
+
+impl FusedIterator for Words<'_> {}
+
+/// Return an iterator over the indices in [`MN_WORDS`] of the words that encode `src`.
+///
+/// ## Example
+/// ```
+/// let indices = mnemonic::bytes_to_indices(&[101, 2, 240, 6]).collect::<Vec<_>>();
+/// assert_eq!(indices, [217, 38, 44]);
+/// assert_eq!(mnemonic::MN_WORDS[217], b"digital");
+/// ```
+pub fn bytes_to_indices(src: &[u8]) -> Indices<'_> {
+    Indices { src, range: 0..mn_words_required(src) }
+}
+
+/// An iterator over the indices of the words that encode a byte slice, returned by
+/// [`bytes_to_indices`].
+#[derive(Clone, Debug)]
+pub struct Indices<'a> {
+    src: &'a [u8],
+    range: Range<usize>,
+}
+
+impl Iterator for Indices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.range.next().map(|n| mn_encode_word_index(self.src, n, MN_BASE) as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        self.range.nth(n).map(|n| mn_encode_word_index(self.src, n, MN_BASE) as usize)
+    }
+}
+
+impl DoubleEndedIterator for Indices<'_> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.range.next_back().map(|n| mn_encode_word_index(self.src, n, MN_BASE) as usize)
+    }
+}
+
+impl ExactSizeIterator for Indices<'_> {}
+
+impl FusedIterator for Indices<'_> {}
+
+/// Decode a sequence of indices in [`MN_WORDS`] into bytes.
+///
+/// This is the inverse of [`bytes_to_indices`], and checks the indices the same way that
+/// [`decode`] checks words.  Positions in errors have no byte range.
+///
+/// ## Example
+/// ```
+/// assert_eq!(mnemonic::indices_to_bytes([217, 38, 44]).unwrap(), [101, 2, 240, 6]);
+///
+/// let e = mnemonic::indices_to_bytes([217, 38, 5000]).unwrap_err();
+/// assert_eq!(e.to_string(), "word index 5000 at word 3 is out of range");
+/// ```
+pub fn indices_to_bytes<I>(indices: I) -> Result<Vec<u8>>
+    where I: IntoIterator<Item = usize>
+{
+    let mut dest = Vec::new();
+    let indices = indices.into_iter().enumerate().map(|(n, index)| {
+        let position = Position { word: n, bytes: None };
+        match word_for_index(index) {
+            Some(word) => Ok((index as u32, word.as_bytes(), position)),
+            None => Err(InvalidIndex { index, position }),
+        }
+    });
+    mn_decode_indices(indices, MN_BASE, |bytes| {
+        dest.extend_from_slice(bytes);
+        Ok(())
+    })?;
+    Ok(dest)
+}
+
+/// Return the word at `index` in [`MN_WORDS`], or `None` if `index` is out of range.
+///
+/// ## Example
+/// ```
+/// assert_eq!(mnemonic::word_for_index(217), Some("digital"));
+/// assert_eq!(mnemonic::word_for_index(5000), None);
+/// ```
+pub fn word_for_index(index: usize) -> Option<&'static str> {
+    // Every word in the list is ASCII.
+    MN_WORDS.get(index).map(|word| str::from_utf8(word).unwrap())
+}
+
+/// Return the index of `word` in [`MN_WORDS`], or `None` if it is not in the list.
+///
+/// The word must match exactly, in lowercase.
+///
+/// ## Example
+/// ```
+/// assert_eq!(mnemonic::index_for_word("digital"), Some(217));
+/// assert_eq!(mnemonic::index_for_word("Digital"), None);
+/// ```
+pub fn index_for_word<W: AsRef<[u8]>>(word: W) -> Option<usize> {
+    mn_word_index(word.as_ref()).map(|i| i as usize)
+}
+
+/// Pass each piece of the encoding of `src` (separators and words) to `emit`.
+fn mn_encode<E, F>(src: &[u8], format: &Format, emit: F) -> result::Result<(), E>
+    where F: FnMut(&[u8]) -> result::Result<(), E>
+{
+    mn_encode_with(src, format, MN_BASE, None, |index| MN_WORDS[index as usize], emit)
+}
+
+/// Like [`mn_encode`], but with digits in the given `base`, followed by extra words with the
+/// indices in `trailing`, and using `word` to get the text for each word index.
+fn mn_encode_with<'w, E, T, M, F>(src: &[u8], format: &Format, base: u32, trailing: T, mut word: M,
+                                  mut emit: F) -> result::Result<(), E>
+    where T: IntoIterator<Item = u32>,
+          M: FnMut(u32) -> &'w [u8],
+          F: FnMut(&[u8]) -> result::Result<(), E>
+{
+    let indices = (0..mn_words_required(src)).map(|n| mn_encode_word_index(src, n, base));
+    let mut n = 0;
+    for index in indices.chain(trailing) {
+        format.write_word(n, word(index), &mut emit)?;
+        n += 1;
+    }
+    format.finish(n, &mut emit)
 }
 
 /// The number of words required to encode data using mnemonic encoding.
 fn mn_words_required(src: &[u8]) -> usize {
-    (src.len() + 1) * 3 / 4
+    words_required(src.len())
 }
 
 /// Return the `n`th word in the encoding of `src`.
 fn mn_encode_word(src: &[u8], n: usize) -> &'static [u8] {
+    MN_WORDS[mn_encode_word_index(src, n, MN_BASE) as usize]
+}
+
+/// Return the index of the `n`th word in the encoding of `src`, using digits in the given
+/// `base`.  Remainder words follow the `base` ordinary words.
+fn mn_encode_word_index(src: &[u8], n: usize, base: u32) -> u32 {
     let offset = n / 3 * 4;
     let mut x = 0;
     for (i, b) in src[offset..].iter().take(4).enumerate() {
@@ -447,16 +769,16 @@ fn mn_encode_word(src: &[u8], n: usize) -> &'static [u8] {
         2 => {
             // special case for 24 bits: use one of the 7 3-letter words
             if src.len() - offset == 3 {
-                extra = MN_BASE;
+                extra = base;
             }
-            x /= MN_BASE * MN_BASE;
+            x /= base * base;
         }
         1 => {
-            x /= MN_BASE;
+            x /= base;
         }
         _ => {}
     }
-    MN_WORDS[(x % MN_BASE + extra) as usize]
+    x % base + extra
 }
 
 /// Decode the mnemonic string `src` into bytes, and write the bytes to `dest`.
@@ -471,21 +793,192 @@ fn mn_encode_word(src: &[u8], n: usize) -> &'static [u8] {
 ///
 /// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
 /// ```
-pub fn decode<S, W>(src: S, mut dest: W) -> Result<usize>
+#[cfg(feature = "std")]
+pub fn decode<S, W>(src: S, dest: W) -> Result<usize>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    Decoder::new().decode(src, dest)
+}
+
+/// Decode the mnemonic string `src`, which must be in the format given by the template `format`,
+/// and write the bytes to `dest`.
+///
+/// This is stricter than [`decode`]; see [`Format::decode`].
+///
+/// ## Example
+///
+/// ```
+/// let mut dest = Vec::<u8>::new();
+/// mnemonic::decode_with_format("digital apollo aroma\nrival artist rebel", "x x x\n", &mut dest)
+///     .unwrap();
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+///
+/// assert!(mnemonic::decode_with_format("digital apollo  aroma", "x x x\n", Vec::new()).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_with_format<S, F, W>(src: S, format: F, dest: W) -> Result<usize>
+    where S: AsRef<[u8]>,
+          F: AsRef<[u8]>,
+          W: Write
+{
+    Format::from_bytes(format.as_ref())?.decode(src, dest)
+}
+
+/// Decode the mnemonic string `src` into bytes using a custom word list, and write the bytes to
+/// `dest`.
+///
+/// ## Panics
+///
+/// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+#[cfg(feature = "std")]
+pub fn decode_with_wordlist<S, L, W>(src: S, wordlist: &L, dest: W) -> Result<usize>
     where S: AsRef<[u8]>,
+          L: Wordlist + ?Sized,
           W: Write
+{
+    Decoder::new().wordlist(wordlist).decode(src, dest)
+}
+
+/// Decode the mnemonic string `src` and return the bytes as a `Vec`.
+///
+/// Unlike [`decode`], this is available without the `std` feature.
+///
+/// ## Example
+///
+/// ```
+/// let decoded = mnemonic::decode_to_vec("digital-apollo-aroma--rival-artist-rebel").unwrap();
+/// assert_eq!(decoded, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// ```
+pub fn decode_to_vec<S: AsRef<[u8]>>(src: S) -> Result<Vec<u8>> {
+    Decoder::new().decode_to_vec(src)
+}
+
+/// Decode the mnemonic string `src` into `dest`, without allocating, and return the number of
+/// bytes decoded.
+///
+/// Fails with [`Error::BufferTooSmall`] if the decoded data does not fit in `dest`.  A buffer of
+/// [`decoded_len_upper_bound`] bytes is always large enough.
+///
+/// ## Example
+///
+/// ```
+/// let mut buf = [0; 16];
+/// let len = mnemonic::decode_to_slice("digital-apollo-aroma", &mut buf).unwrap();
+/// assert_eq!(&buf[..len], [101, 2, 240, 6]);
+/// ```
+pub fn decode_to_slice<S: AsRef<[u8]>>(src: S, dest: &mut [u8]) -> Result<usize> {
+    Decoder::new().decode_to_slice(src, dest)
+}
+
+/// Decode the mnemonic string `src` into an array of exactly `N` bytes, without allocating.
+///
+/// Fails with [`Error::BufferTooSmall`] if the decoded data is longer than `N` bytes, or
+/// [`Error::WrongLength`] if it is shorter.
+///
+/// ## Example
+///
+/// ```
+/// let key: [u8; 4] = mnemonic::decode_to_array("digital-apollo-aroma").unwrap();
+/// assert_eq!(key, [101, 2, 240, 6]);
+///
+/// assert!(mnemonic::decode_to_array::<8>("digital-apollo-aroma").is_err());
+/// ```
+pub fn decode_to_array<const N: usize>(src: impl AsRef<[u8]>) -> Result<[u8; N]> {
+    Decoder::new().decode_to_array(src)
+}
+
+/// Return the greatest number of bytes that `word_count` words can decode to.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(mnemonic::decoded_len_upper_bound(3), 4);
+/// assert_eq!(mnemonic::decoded_len_upper_bound(4), 5);
+/// ```
+pub fn decoded_len_upper_bound(word_count: usize) -> usize {
+    *decoded_len_range(word_count).end()
+}
+
+/// Return the possible numbers of bytes that `word_count` words can decode to.
+///
+/// Every 3 words hold 4 bytes, and 1 or 2 extra words hold 1 or 2 bytes.  A multiple of 3
+/// words can also hold one byte less, when the last word is a 24-bit remainder word.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(mnemonic::decoded_len_range(2), 2..=2);
+/// assert_eq!(mnemonic::decoded_len_range(6), 7..=8);
+/// ```
+pub fn decoded_len_range(word_count: usize) -> RangeInclusive<usize> {
+    let len = (word_count / 3).saturating_mul(4).saturating_add(word_count % 3);
+    match word_count % 3 {
+        0 if word_count > 0 => len - 1..=len,
+        _ => len..=len,
+    }
+}
+
+/// Return the number of words needed to encode `len` bytes.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(mnemonic::words_required(8), 6);
+/// assert_eq!(mnemonic::words_required(3), 3);
+/// ```
+pub fn words_required(len: usize) -> usize {
+    len / 4 * 3 + len % 4
+}
+
+/// Return the greatest length in bytes of the string encoding `len` bytes in the default
+/// format.
+///
+/// See [`Format::max_encoded_len`].
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(mnemonic::max_encoded_len(4), 23);
+/// assert_eq!(mnemonic::to_string([101, 2, 240, 6]).len(), 20);
+/// ```
+pub fn max_encoded_len(len: usize) -> usize {
+    Format::default().max_encoded_len(len)
+}
+
+/// Decode `src`, passing each decoded chunk of bytes to `emit`.
+///
+/// `resolve` is called to find the index of each word in a word list with the given `base`.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+fn mn_decode<R, F>(src: &[u8], base: u32, mut resolve: R, emit: F) -> Result<usize>
+    where R: FnMut(&[u8], &Position) -> Result<u32>,
+          F: FnMut(&[u8]) -> Result<()>
+{
+    let indices = mn_words(src).enumerate().map(|(n, (start, word))| {
+        let position = Position { word: n, bytes: Some(start..start + word.len()) };
+        let i = resolve(word, &position)?;
+        Ok((i, word, position))
+    });
+    mn_decode_indices(indices, base, emit)
+}
+
+/// Decode a sequence of word indices, passing each decoded chunk of bytes to `emit`.
+///
+/// Each item of `indices` is the index of a word in a word list with the given `base`, along
+/// with the text and position of the word for error reporting.
+fn mn_decode_indices<'a, I, F>(indices: I, base: u32, mut emit: F) -> Result<usize>
+    where I: IntoIterator<Item = Result<(u32, &'a [u8], Position)>>,
+          F: FnMut(&[u8]) -> Result<()>
 {
     let mut offset = 0; // Number of bytes decoded so far.
     let mut x = 0u32;   // We decode each 4-byte chunk into this 32-bit value.
 
-    let words = src.as_ref().split(|c| !c.is_ascii_alphabetic())
-                            .filter(|w| !w.is_empty());
-    for word in words {
-        let i = *mn_word_index().get(word).ok_or(UnrecognizedWord)?;
-        mn_decode_word_index(i, &mut x, &mut offset)?;
+    for item in indices {
+        let (i, word, position) = item?;
+        mn_decode_word_index(i, base, &mut x, &mut offset).map_err(|e| e.at(word, position))?;
         if offset % 4 == 0 {
             // Finished decoding this 4-byte chunk.
-            dest.write_all(&x.to_le_bytes())?;
+            emit(&x.to_le_bytes())?;
             x = 0;
         }
     }
@@ -493,33 +986,47 @@ pub fn decode<S, W>(src: S, mut dest: W) -> Result<usize>
     let remainder = offset % 4;
     if remainder > 0 {
         let buf = x.to_le_bytes();
-        dest.write_all(&buf[..remainder])?;
+        emit(&buf[..remainder])?;
     }
     mn_decode_finish(x, remainder)?;
     Ok(offset)
 }
 
-fn mn_decode_word_index(index: u32, x: &mut u32, offset: &mut usize) -> Result<()> {
-    if index >= MN_BASE && *offset % 4 != 2 {
-        return Err(UnexpectedRemainderWord)
+/// Find the index of `word` in the MN_WORDS array, requiring an exact match.
+fn mn_resolve_exact(word: &[u8], position: &Position) -> Result<u32> {
+    mn_word_index(word).ok_or_else(|| WordError::UnrecognizedWord.at(word, position.clone()))
+}
+
+/// Split `src` into words at non-alphabetic bytes, yielding the byte offset of each word along
+/// with the word itself.
+fn mn_words(src: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    src.split(|c| !c.is_ascii_alphabetic())
+       .filter(|w| !w.is_empty())
+       .map(move |w| (w.as_ptr() as usize - src.as_ptr() as usize, w))
+}
+
+/// Add the word with the given index, in a word list with the given `base`, to the chunk being
+/// decoded into `x`, and advance `offset` past any bytes it completes.
+fn mn_decode_word_index(index: u32, base: u32, x: &mut u32, offset: &mut usize)
+    -> result::Result<(), WordError>
+{
+    if index >= base && *offset % 4 != 2 {
+        return Err(WordError::UnexpectedRemainderWord)
     }
     match *offset % 4 {
-        3 => return Err(DataPastRemainder),
-        2 if index >= MN_BASE => {
+        3 => return Err(WordError::DataPastRemainder),
+        2 if index >= base => {
             // 24-bit remainder
-            *x += (index - MN_BASE) * MN_BASE * MN_BASE;
+            *x = mn_add_digit(*x, index - base, base).ok_or(WordError::InvalidEncoding)?;
             *offset += 1; // *offset%4 == 3 for next time
         }
         2 => {
             // catch invalid encodings
-            if index >= 1625 || (index == 1624 && *x > 1312671) {
-                return Err(InvalidEncoding)
-            }
-            *x += index * MN_BASE * MN_BASE;
+            *x = mn_add_digit(*x, index, base).ok_or(WordError::InvalidEncoding)?;
             *offset += 2;
         }
         1 => {
-            *x += index * MN_BASE;
+            *x += index * base;
             *offset += 1;
         }
         0 => {
@@ -531,6 +1038,13 @@ fn mn_decode_word_index(index: u32, x: &mut u32, offset: &mut usize) -> Result<(
     Ok(())
 }
 
+/// Add `digit` as the third base-`base` digit of `x`, or return `None` if the result does not fit
+/// in 32 bits.
+fn mn_add_digit(x: u32, digit: u32, base: u32) -> Option<u32> {
+    let sum = x as u64 + digit as u64 * base as u64 * base as u64;
+    u32::try_from(sum).ok()
+}
+
 fn mn_decode_finish(x: u32, remainder: usize) -> Result<()> {
     if (remainder == 2 && x > 0xFFFF) || (remainder == 1 && x > 0xFF) {
         return Err(UnexpectedRemainder)
@@ -539,12 +1053,24 @@ fn mn_decode_finish(x: u32, remainder: usize) -> Result<()> {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod tests {
     use quickcheck::quickcheck;
     use super::*;
+    #[cfg(feature = "std")]
     use std::str;
 
     #[test]
+    fn test_word_index() {
+        for (i, word) in MN_WORDS.iter().enumerate() {
+            assert_eq!(mn_word_index(word), Some(i as u32));
+        }
+        assert_eq!(mn_word_index(b"aplolo"), None);
+        assert_eq!(MN_WORDS.iter().map(|w| w.len()).max(), Some(MN_MAX_WORD_LEN));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_encode() {
         let mut w: Vec<u8> = vec![];
         encode(&[101, 2, 240, 6, 108, 11, 20, 97], &mut w).unwrap();
@@ -552,6 +1078,15 @@ mod tests {
         assert_eq!(s, "digital-apollo-aroma--rival-artist-rebel");
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_encode_invalid_format() {
+        let e = encode_with_format([1, 2, 3], " ", &mut Vec::new()).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+        assert!(matches!(Error::from(e), InvalidFormat { .. }));
+        assert!(encode_with_format_to_fmt([1, 2, 3], "--", &mut String::new()).is_err());
+    }
+
     #[test]
     fn test_to_string() {
         let src = [101, 2, 240, 6, 108, 11, 20, 97];
@@ -559,6 +1094,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_decode() {
         let mut dest: Vec<u8> = vec![];
         let src = "digital-apollo-aroma--rival-artist-rebel";
@@ -573,6 +1109,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_decode_24bit() {
         let mut dest: Vec<u8> = vec![];
         let src = "consul-quiet-fax";
@@ -580,7 +1117,107 @@ mod tests {
         assert_eq!(dest, [0x01, 0xE2, 0x40]);
     }
 
+    #[test]
+    fn test_decode_to_vec() {
+        let decoded = decode_to_vec("consul-quiet-fax").unwrap();
+        assert_eq!(decoded, [0x01, 0xE2, 0x40]);
+    }
+
+    #[test]
+    fn test_decode_error_position() {
+        let e = decode_to_vec("digital-apollo-aroma--rival-aplolo").unwrap_err();
+        match e {
+            UnrecognizedWord { ref word, ref position } => {
+                assert_eq!(word, "aplolo");
+                assert_eq!(*position, Position { word: 4, bytes: Some(28..34) });
+            }
+            _ => panic!("unexpected error {:?}", e),
+        }
+        assert_eq!(e.to_string(), "unrecognized word 'aplolo' at word 5 (bytes 28..34)");
+    }
+
+    #[test]
+    fn test_decode_data_past_remainder() {
+        let e = decode_to_vec("consul-quiet-fax-digital").unwrap_err();
+        assert_eq!(e.to_string(),
+                   "unexpected word 'digital' past 24-bit remainder at word 4 (bytes 17..24)");
+    }
+
+    #[test]
+    fn test_words() {
+        let src = [101, 2, 240, 6, 108, 11, 20, 97];
+        let words = words(&src);
+        assert_eq!(words.len(), 6);
+        assert_eq!(words.clone().rev().collect::<Vec<_>>(),
+                   ["rebel", "artist", "rival", "aroma", "apollo", "digital"]);
+        assert_eq!(words.clone().nth(4), Some("artist"));
+        assert_eq!(words.clone().skip(5).len(), 1);
+        assert_eq!(super::words(&[]).next(), None);
+        assert_eq!(super::words(&[0x01, 0xE2, 0x40]).collect::<Vec<_>>(), ["consul", "quiet", "fax"]);
+    }
+
+    #[test]
+    fn test_indices() {
+        let src = [0x01, 0xE2, 0x40];
+        let indices = bytes_to_indices(&src).collect::<Vec<_>>();
+        assert_eq!(indices.iter().map(|&i| word_for_index(i).unwrap()).collect::<Vec<_>>(),
+                   ["consul", "quiet", "fax"]);
+        assert_eq!(indices.iter().map(|&i| index_for_word(MN_WORDS[i])).collect::<Vec<_>>(),
+                   indices.iter().map(|&i| Some(i)).collect::<Vec<_>>());
+        assert_eq!(indices_to_bytes(indices).unwrap(), src);
+        assert_eq!(word_for_index(MN_WORDS_LEN), None);
+
+        let fax = index_for_word("fax").unwrap();
+        let e = indices_to_bytes([fax]).unwrap_err();
+        assert_eq!(e.to_string(), "unexpected 24-bit remainder word 'fax' at word 1");
+        assert!(matches!(indices_to_bytes([1625, 1625]), Err(UnexpectedRemainder)));
+    }
+
+    #[test]
+    fn test_decoded_len_range() {
+        assert_eq!(decoded_len_range(0), 0..=0);
+        assert_eq!(decoded_len_range(1), 1..=1);
+        assert_eq!(decoded_len_range(3), 3..=4);
+        assert_eq!(decoded_len_range(usize::MAX), usize::MAX - 1..=usize::MAX);
+        for len in 0..100 {
+            assert!(decoded_len_range(words_required(len)).contains(&len));
+        }
+        assert_eq!(words_required(usize::MAX), usize::MAX / 4 * 3 + 3);
+    }
+
+    #[test]
+    fn test_decoded_len_upper_bound() {
+        assert_eq!(decoded_len_upper_bound(0), 0);
+        assert_eq!(decoded_len_upper_bound(2), 2);
+        assert_eq!(decoded_len_upper_bound(6), 8);
+        assert_eq!(decoded_len_upper_bound(usize::MAX), usize::MAX);
+    }
+
     quickcheck! {
+        fn quickcheck_max_encoded_len(src: Vec<u8>) -> bool {
+            let encoded = to_string(&src);
+            encoded.len() <= max_encoded_len(src.len())
+                && words_required(src.len()) == encoded.split('-').filter(|w| !w.is_empty()).count()
+        }
+
+        fn quickcheck_decoded_len_upper_bound(src: Vec<u8>) -> bool {
+            let mut buf = vec![0; decoded_len_upper_bound(mn_words_required(&src))];
+            let len = decode_to_slice(to_string(&src), &mut buf).unwrap();
+            buf[..len] == src[..] && len + 1 >= buf.len()
+        }
+
+        fn quickcheck_words(src: Vec<u8>) -> bool {
+            let words = words(&src);
+            let mut s = String::new();
+            encode_with_format_to_fmt(&src, "x ", &mut s).unwrap();
+            words.len() == mn_words_required(&src) && words.collect::<Vec<_>>().join(" ") == s
+        }
+
+        fn quickcheck_indices(src: Vec<u8>) -> bool {
+            indices_to_bytes(bytes_to_indices(&src)).unwrap() == src
+        }
+
+        #[cfg(feature = "std")]
         fn quickcheck_round_trip(src: Vec<u8>) -> bool {
             let encoded = to_string(&src);
             let mut decoded = Vec::<u8>::new();
@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{decode_to_vec, display, Error};
+
+/// Bytes that are displayed and parsed as a mnemonic string.
+///
+/// A `Mnemonic` is formatted with [`display`], and parsed with [`decode_to_vec`].  It is
+/// compared, ordered and hashed by the value of its bytes.
+///
+/// ## Example
+///
+/// ```
+/// use mnemonic::Mnemonic;
+///
+/// let id: Mnemonic = "digital-apollo-aroma".parse().unwrap();
+/// assert_eq!(id.as_bytes(), [101, 2, 240, 6]);
+/// assert_eq!(id.to_string(), "digital-apollo-aroma");
+///
+/// assert!("digital-apolo".parse::<Mnemonic>().is_err());
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mnemonic(Vec<u8>);
+
+impl Mnemonic {
+    /// A mnemonic for the given bytes.
+    pub fn new<B: Into<Vec<u8>>>(bytes: B) -> Self {
+        Mnemonic(bytes.into())
+    }
+
+    /// The bytes of this mnemonic.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Convert this mnemonic into its bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&display(&self.0), f)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic(\"{}\")", self)
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        decode_to_vec(s).map(Mnemonic)
+    }
+}
+
+impl AsRef<[u8]> for Mnemonic {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Mnemonic {
+    fn from(bytes: Vec<u8>) -> Self {
+        Mnemonic(bytes)
+    }
+}
+
+impl From<&[u8]> for Mnemonic {
+    fn from(bytes: &[u8]) -> Self {
+        Mnemonic(bytes.to_vec())
+    }
+}
+
+impl From<Mnemonic> for Vec<u8> {
+    fn from(mnemonic: Mnemonic) -> Self {
+        mnemonic.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Mnemonic {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Mnemonic {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde::deserialize(deserializer).map(Mnemonic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_mnemonic() {
+        let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+        let s = "digital-apollo-aroma--rival-artist-rebel";
+        let mnemonic = Mnemonic::new(bytes);
+        assert_eq!(mnemonic.to_string(), s);
+        assert_eq!(format!("{:#}", mnemonic), s.to_uppercase());
+        assert_eq!(format!("{:?}", mnemonic), format!("Mnemonic(\"{}\")", s));
+        assert_eq!(s.parse::<Mnemonic>().unwrap(), mnemonic);
+        assert_eq!(mnemonic.as_ref(), bytes);
+        assert_eq!(Vec::from(mnemonic), bytes);
+
+        let e = "digital apolo".parse::<Mnemonic>().unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'apolo' at word 2 (bytes 8..13)");
+        assert_eq!("".parse::<Mnemonic>().unwrap(), Mnemonic::default());
+    }
+
+    #[test]
+    fn test_ord() {
+        let a = Mnemonic::from(&[1, 2][..]);
+        let b = Mnemonic::from(&[1, 3][..]);
+        assert!(a < b);
+        assert!(Mnemonic::new([]) < a);
+        assert_eq!(a.clone().max(b.clone()), b);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        assert_tokens(&Mnemonic::new([101, 2]).readable(), &[Token::Str("radio-academy")]);
+        assert_tokens(&Mnemonic::new([101, 2]).compact(), &[Token::Bytes(&[101, 2])]);
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::str;
+#[cfg(feature = "std")]
+use std::io::Write;
+#[cfg(feature = "unicode")]
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+#[cfg(feature = "std")]
+use crate::{Decoder, Result};
+use crate::{Error, Position};
+
+/// Decode the mnemonic string `src` into bytes, ignoring case and invisible characters, and
+/// write the bytes to `dest`.
+///
+/// Words are converted to lowercase before they are looked up, and zero-width characters such
+/// as U+200B ZERO WIDTH SPACE and U+00AD SOFT HYPHEN are removed.  With the `unicode` feature,
+/// the input is also put in Unicode compatibility decomposed form (NFKD) with diacritics
+/// removed, so that full-width letters like `ａ` and accented letters like `á` are read as `a`.
+///
+/// Positions in errors refer to the bytes of `src`, but the words in errors are normalized.  Use
+/// [`decode`](crate::decode) to accept only lowercase words.
+///
+/// ## Example
+///
+/// ```
+/// let mut dest = Vec::<u8>::new();
+/// mnemonic::decode_normalized("Digital-APOLLO-aro\u{200B}ma", &mut dest).unwrap();
+/// assert_eq!(dest, [101, 2, 240, 6]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_normalized<S, W>(src: S, dest: W) -> Result<usize>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    Decoder::new().normalize(true).decode(src, dest)
+}
+
+/// Normalized text, along with the range of the original text that each byte came from.
+pub(crate) struct Normalized {
+    pub(crate) text: Vec<u8>,
+    origins: Vec<Range<usize>>,
+    /// The length of the original text.
+    len: usize,
+}
+
+impl Normalized {
+    pub(crate) fn new(src: &[u8]) -> Self {
+        let mut normalized = Normalized {
+            text: Vec::with_capacity(src.len()),
+            origins: Vec::with_capacity(src.len()),
+            len: src.len(),
+        };
+        let mut offset = 0;
+        while offset < src.len() {
+            let rest = &src[offset..];
+            let (valid, invalid) = match str::from_utf8(rest) {
+                Ok(valid) => (valid, 0),
+                Err(e) => {
+                    let valid = str::from_utf8(&rest[..e.valid_up_to()]).unwrap();
+                    (valid, e.error_len().unwrap_or(rest.len() - e.valid_up_to()))
+                }
+            };
+            for (i, c) in valid.char_indices() {
+                let start = offset + i;
+                normalized.push_char(c, start..start + c.len_utf8());
+            }
+            offset += valid.len();
+            if invalid > 0 {
+                // Invalid UTF-8 separates words, like any other non-letter.
+                normalized.push_char(char::REPLACEMENT_CHARACTER, offset..offset + invalid);
+                offset += invalid;
+            }
+        }
+        normalized
+    }
+
+    /// Append the normalized form of `c`, which came from `origin` in the original text.
+    fn push_char(&mut self, c: char, origin: Range<usize>) {
+        if is_ignored(c) {
+            return
+        }
+        #[cfg(feature = "unicode")]
+        for c in core::iter::once(c).nfkd().filter(|&c| !is_combining_mark(c)) {
+            self.push_lowercase(c, &origin);
+        }
+        #[cfg(not(feature = "unicode"))]
+        self.push_lowercase(c, &origin);
+    }
+
+    fn push_lowercase(&mut self, c: char, origin: &Range<usize>) {
+        let mut buf = [0; 4];
+        for &b in c.to_ascii_lowercase().encode_utf8(&mut buf).as_bytes() {
+            self.text.push(b);
+            self.origins.push(origin.clone());
+        }
+    }
+
+    /// Change the position in `e`, if any, to refer to the original text.
+    pub(crate) fn map_error(&self, mut e: Error) -> Error {
+        if let Some(position) = e.position_mut() {
+            self.map_position(position);
+        }
+        e
+    }
+
+    /// Change `position` to refer to the original text.
+    pub(crate) fn map_position(&self, position: &mut Position) {
+        position.bytes = position.bytes.take().map(|bytes| self.original(bytes));
+    }
+
+    /// The range of the original text that `bytes` of the normalized text came from.
+    fn original(&self, bytes: Range<usize>) -> Range<usize> {
+        let start = self.origins.get(bytes.start).map_or(self.len, |origin| origin.start);
+        match bytes.end.checked_sub(1) {
+            Some(last) if bytes.end > bytes.start => start..self.origins[last].end,
+            _ => start..start,
+        }
+    }
+}
+
+/// Whether `c` is an invisible character that should be left out entirely.
+fn is_ignored(c: char) -> bool {
+    matches!(c, '\u{00AD}' | '\u{180E}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use crate::{Decoder, Result};
+
+    fn decode<S: AsRef<[u8]>>(src: S) -> Result<Vec<u8>> {
+        Decoder::new().normalize(true).decode_to_vec(src)
+    }
+
+    #[test]
+    fn test_decode_normalized() {
+        let bytes = [101, 2, 240, 6, 108, 11, 20, 97];
+        assert_eq!(decode("Digital-APOLLO-aroma--Rival-ArTiSt-rebel").unwrap(), bytes);
+        assert_eq!(decode("\u{FEFF}digi\u{200B}tal apol\u{00AD}lo aroma").unwrap(), &bytes[..4]);
+
+        let e = decode("DIGITAL a\u{200B}b").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'ab' at word 2 (bytes 8..13)");
+        let e = decode(b"digital\xFF\xFEapolo").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'apolo' at word 2 (bytes 9..14)");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn test_decode_unicode() {
+        let bytes = [101, 2, 240, 6];
+        assert_eq!(decode("Ｄｉｇｉｔａｌ ápollo\u{00A0}aróma").unwrap(), bytes);
+        assert_eq!(decode("digital a\u{0301}pollo aroma").unwrap(), bytes);
+
+        let e = decode("Ｄｉｇｉｔａｌ xyz").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'xyz' at word 2 (bytes 22..25)");
+        let e = decode("digital ápolo").unwrap_err();
+        assert_eq!(e.to_string(), "unrecognized word 'apolo' at word 2 (bytes 8..14)");
+    }
+}
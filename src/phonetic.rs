@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use core::slice;
+use std::io::Write;
+use std::sync::OnceLock;
+
+use crate::error::WordError;
+use crate::fuzzy::mn_edit_distance;
+use crate::{mn_decode, mn_word_index, Correction, Error, Position, Result, MN_BASE, MN_WORDS};
+
+/// Decode the mnemonic string `src` into bytes, replacing unrecognized words with words that
+/// sound the same, and write the bytes to `dest`.
+///
+/// This is meant for words transcribed by ear, such as "filipps" for "philips".  Each word
+/// that is not in the word list is reduced to a phonetic key (similar to Metaphone), and
+/// replaced by the word in the list with the same key.  If several words share that key, the
+/// one with the smallest edit distance is chosen.  Remaining ties go to the word that shares
+/// the longest ending with the input, since a listener is more likely to misspell a vowel in
+/// the middle of a word (as in "rodio" for "radio") than a familiar ending such as "-io".  A
+/// word that still sounds like more than one word equally well is rejected with
+/// [`Error::AmbiguousWord`].
+///
+/// Returns the substitutions that were made.
+///
+/// ## Example
+///
+/// ```
+/// let src = "consul-quite-faks";
+///
+/// let mut dest = Vec::<u8>::new();
+/// let corrections = mnemonic::decode_phonetic(src, &mut dest).unwrap();
+///
+/// assert_eq!(dest, [0x01, 0xE2, 0x40]);
+/// assert_eq!(corrections[0].corrected, "quiet");
+/// assert_eq!(corrections[1].corrected, "fax");
+/// ```
+pub fn decode_phonetic<S, W>(src: S, mut dest: W) -> Result<Vec<Correction>>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    let mut corrections = Vec::new();
+    mn_decode(src.as_ref(), MN_BASE,
+              |word, position| mn_resolve_phonetic(word, position, &mut corrections),
+              |bytes| Ok(dest.write_all(bytes)?))?;
+    Ok(corrections)
+}
+
+/// The words in the MN_WORDS array, sorted by phonetic key.
+struct PhoneticIndex {
+    entries: Vec<(Vec<u8>, u32)>,
+}
+
+impl PhoneticIndex {
+    /// The shared index, built the first time it is needed.
+    fn get() -> &'static Self {
+        static INDEX: OnceLock<PhoneticIndex> = OnceLock::new();
+        INDEX.get_or_init(PhoneticIndex::new)
+    }
+
+    fn new() -> Self {
+        let mut entries: Vec<_> = MN_WORDS.iter()
+                                          .enumerate()
+                                          .map(|(i, word)| (mn_phonetic_key(word), i as u32))
+                                          .collect();
+        entries.sort();
+        PhoneticIndex { entries }
+    }
+
+    /// The entries for all words with phonetic key `key`.
+    fn lookup(&self, key: &[u8]) -> &[(Vec<u8>, u32)] {
+        let start = self.entries.partition_point(|(k, _)| &k[..] < key);
+        let end = self.entries.partition_point(|(k, _)| &k[..] <= key);
+        &self.entries[start..end]
+    }
+}
+
+/// Find the index of `word` in the MN_WORDS array, or of the word that sounds most like it.
+///
+/// Any substitution is appended to `corrections`.
+pub(crate) fn mn_resolve_phonetic(word: &[u8], position: &Position,
+                                  corrections: &mut Vec<Correction>) -> Result<u32> {
+    if let Some(i) = mn_word_index(word) {
+        return Ok(i)
+    }
+    let index = PhoneticIndex::get();
+    let lowercase = word.to_ascii_lowercase();
+    let mut best = usize::MAX;
+    let mut candidates = Vec::new();
+    for &(_, i) in index.lookup(&mn_phonetic_key(word)) {
+        let distance = mn_edit_distance(&lowercase, MN_WORDS[i as usize]);
+        if distance < best {
+            best = distance;
+            candidates.clear();
+        }
+        if distance == best {
+            candidates.push(i);
+        }
+    }
+    // Break ties in favor of the words that share the longest ending with `word`.
+    let suffix = |i: &u32| mn_common_suffix_len(&lowercase, MN_WORDS[*i as usize]);
+    let longest = candidates.iter().map(suffix).max().unwrap_or(0);
+    candidates.retain(|i| suffix(i) == longest);
+    match candidates[..] {
+        [] => Err(WordError::UnrecognizedWord.at(word, position.clone())),
+        [i] => {
+            corrections.push(Correction::new(word, MN_WORDS[i as usize], position));
+            Ok(i)
+        }
+        _ => Err(Error::ambiguous(word, position, candidates.iter().map(|&i| MN_WORDS[i as usize]))),
+    }
+}
+
+/// The number of letters at the end of `a` and `b` that are the same.
+fn mn_common_suffix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().rev().zip(b.iter().rev()).take_while(|(x, y)| x == y).count()
+}
+
+/// Reduce `word` to a key that is the same for words that sound alike.
+///
+/// This is a simplified form of the Metaphone algorithm: vowels after the first letter are
+/// dropped, consonants that sound alike map to the same letter, silent letters are dropped, and
+/// repeated sounds are collapsed.
+fn mn_phonetic_key(word: &[u8]) -> Vec<u8> {
+    let word = word.to_ascii_lowercase();
+    let at = |i: usize| word.get(i).copied().unwrap_or(0);
+    let is_vowel = |c: u8| matches!(c, b'a' | b'e' | b'i' | b'o' | b'u');
+    let is_soft = |c: u8| matches!(c, b'e' | b'i' | b'y');
+
+    let mut key = Vec::new();
+    // Skip silent initial letters.
+    let start = match (at(0), at(1)) {
+        (b'g' | b'k' | b'p', b'n') | (b'w', b'r') | (b'p', b's') => 1,
+        _ => 0,
+    };
+    let mut i = start;
+    while i < word.len() {
+        let (prev, next) = (if i > 0 { word[i - 1] } else { 0 }, at(i + 1));
+        let code: &[u8] = match word[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => if i == start { b"a" } else { b"" },
+            b'b' if prev == b'm' && i + 1 == word.len() => b"",
+            b'c' if next == b'h' => { i += 1; b"x" }
+            b'c' if is_soft(next) => b"s",
+            b'q' if next == b'u' => { i += 1; b"kw" }
+            b'c' | b'k' | b'q' => b"k",
+            b'd' if next == b'g' && is_soft(at(i + 2)) => { i += 1; b"j" }
+            b'd' => b"t",
+            b'g' if next == b'h' && !is_vowel(at(i + 2)) => { i += 1; b"" }
+            b'g' if next == b'n' => b"",
+            b'g' if is_soft(next) => b"j",
+            b'g' => b"k",
+            b'h' if !is_vowel(next) || matches!(prev, b'c' | b'g' | b'p' | b's' | b't') => b"",
+            b'p' if next == b'h' => { i += 1; b"f" }
+            b's' if next == b'h' => { i += 1; b"x" }
+            b's' | b't' if next == b'i' && matches!(at(i + 2), b'a' | b'o') => b"x",
+            b't' if next == b'h' => { i += 1; b"0" }
+            b'v' => b"f",
+            b'w' if i == start && next == b'h' => { i += 1; b"w" }
+            b'w' | b'y' if !is_vowel(next) => b"",
+            b'x' => if i == start { b"s" } else { b"ks" },
+            b'z' => b"s",
+            _ => slice::from_ref(&word[i]),
+        };
+        for &c in code {
+            if key.last() != Some(&c) {
+                key.push(c);
+            }
+        }
+        i += 1;
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phonetic_key() {
+        assert_eq!(mn_phonetic_key(b"philips"), b"flps");
+        assert_eq!(mn_phonetic_key(b"filipps"), b"flps");
+        assert_eq!(mn_phonetic_key(b"knight"), b"nt");
+        assert_eq!(mn_phonetic_key(b"cinema"), b"snm");
+        assert_eq!(mn_phonetic_key(b"Whiskey"), mn_phonetic_key(b"wiskee"));
+    }
+
+    #[test]
+    fn test_decode_phonetic() {
+        let mut dest = Vec::new();
+        let corrections = decode_phonetic("filipps kwebek academy", &mut dest).unwrap();
+        assert_eq!(corrections[0].corrected, "philips");
+        assert_eq!(corrections[1].corrected, "quebec");
+        dest.clear();
+        let corrections = decode_phonetic("rodio academy", &mut dest).unwrap();
+        assert_eq!(corrections[0].corrected, "radio");
+        assert_eq!(dest, [101, 2]);
+        assert!(matches!(decode_phonetic("xqzv", &mut dest), Err(Error::UnrecognizedWord { .. })));
+        let e = decode_phonetic("pinto", &mut dest).unwrap_err();
+        assert_eq!(e.to_string(), "ambiguous word 'pinto' at word 1 (bytes 0..5): could be 'paint', 'point'");
+    }
+}
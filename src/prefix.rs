@@ -0,0 +1,140 @@
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::{Error, Position, Result, Wordlist, MN_WORDS, MN_WORDS_SORTED};
+use crate::error::WordError;
+#[cfg(feature = "std")]
+use crate::{mn_encode_with, Decoder, Format, MN_BASE};
+
+/// Return the shortest prefix of word `index` in [`MN_WORDS`] that is not a prefix of any other
+/// word.
+///
+/// If the word is itself a prefix of another word, the whole word is returned.
+///
+/// ## Panics
+///
+/// Panics if `index` is out of bounds for `MN_WORDS`.
+///
+/// ## Example
+///
+/// ```
+/// let i = mnemonic::MN_WORDS.iter().position(|w| w == b"digital").unwrap();
+/// assert_eq!(mnemonic::unique_prefix(i), b"dig");
+/// ```
+pub fn unique_prefix(index: usize) -> &'static [u8] {
+    let word = MN_WORDS[index];
+    // Words sharing a prefix are adjacent in sorted order, so only the neighbors of `word`
+    // need to be compared.
+    let pos = MN_WORDS_SORTED.binary_search_by(|&i| MN_WORDS[i as usize].cmp(word)).unwrap();
+    let common = |i: u16| word.iter().zip(MN_WORDS[i as usize]).take_while(|(a, b)| a == b).count();
+    let before = if pos > 0 { common(MN_WORDS_SORTED[pos - 1]) } else { 0 };
+    let after = MN_WORDS_SORTED.get(pos + 1).map_or(0, |&i| common(i));
+    &word[..(before.max(after) + 1).min(word.len())]
+}
+
+/// Encode the bytes of `src` with a custom template, writing the shortest unique prefix of each
+/// word instead of the whole word.
+///
+/// The result can be decoded with [`decode_prefix`].
+///
+/// ## Example
+///
+/// ```
+/// let mut dest = Vec::<u8>::new();
+/// mnemonic::encode_prefixes([0x01, 0xE2, 0x40], mnemonic::MN_FDEFAULT, &mut dest).unwrap();
+/// assert_eq!(dest, b"cons-quie-fax");
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_prefixes<S, F, W>(src: S, format: F, mut dest: W) -> io::Result<()>
+    where S: AsRef<[u8]>,
+          F: AsRef<[u8]>,
+          W: Write
+{
+    let format = Format::from_bytes(format.as_ref())?;
+    mn_encode_with(src.as_ref(), &format, MN_BASE, None,
+                   |index| unique_prefix(index as usize),
+                   |s| dest.write_all(s))
+}
+
+/// Decode the mnemonic string `src` into bytes, accepting any unambiguous prefix of a word,
+/// and write the bytes to `dest`.
+///
+/// A prefix that matches more than one word is rejected with [`Error::AmbiguousWord`], listing
+/// all of the words it could be.
+///
+/// ## Example
+///
+/// ```
+/// let mut dest = Vec::<u8>::new();
+/// mnemonic::decode_prefix("digi-apol-arom--riva-artis-rebe", &mut dest).unwrap();
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_prefix<S, W>(src: S, dest: W) -> Result<usize>
+    where S: AsRef<[u8]>,
+          W: Write
+{
+    Decoder::new().prefixes(true).decode(src, dest)
+}
+
+/// Find the index of the word in `wordlist` that is equal to `word`, or else the only word that
+/// starts with `word`.
+pub(crate) fn mn_resolve_prefix<L>(wordlist: &L, word: &[u8], position: &Position) -> Result<u32>
+    where L: Wordlist + ?Sized
+{
+    match wordlist.words_with_prefix(word)[..] {
+        [] => Err(WordError::UnrecognizedWord.at(word, position.clone())),
+        [i] => Ok(i),
+        ref candidates => match candidates.iter().find(|&&i| wordlist.word(i) == word) {
+            Some(&i) => Ok(i),
+            None => Err(Error::ambiguous(word, position,
+                                         candidates.iter().map(|&i| wordlist.word(i)))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+
+    fn resolve(word: &str) -> Result<u32> {
+        mn_resolve_prefix(&crate::DefaultWordlist, word.as_bytes(), &Position::default())
+    }
+
+    #[test]
+    fn test_unique_prefix() {
+        for (i, word) in MN_WORDS.iter().enumerate() {
+            let prefix = unique_prefix(i);
+            assert_eq!(resolve(std::str::from_utf8(prefix).unwrap()).unwrap(), i as u32);
+            if prefix.len() < word.len() {
+                let shorter = &prefix[..prefix.len() - 1];
+                assert!(resolve(std::str::from_utf8(shorter).unwrap()).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefix() {
+        assert_eq!(MN_WORDS[resolve("digi").unwrap() as usize], b"digital");
+        assert_eq!(MN_WORDS[resolve("fax").unwrap() as usize], b"fax");
+        assert!(matches!(resolve("digix"), Err(Error::UnrecognizedWord { .. })));
+        match resolve("dia") {
+            Err(Error::AmbiguousWord { candidates, .. }) => {
+                assert_eq!(candidates, ["diagram", "dialog", "diamond", "diana"])
+            },
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    quickcheck! {
+        #[cfg(feature = "std")]
+        fn quickcheck_prefix_round_trip(src: Vec<u8>) -> bool {
+            let mut encoded = Vec::new();
+            encode_prefixes(&src, "x x ", &mut encoded).unwrap();
+            let mut decoded = Vec::new();
+            decode_prefix(encoded, &mut decoded).unwrap();
+            decoded == src
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::io;
+use std::io::prelude::*;
+
+use crate::error::WordError;
+use crate::wordlist::mn_check_wordlist;
+use crate::{mn_decode_finish, mn_decode_word_index, DefaultWordlist, Error, Position, Result,
+            Wordlist};
+
+/// A reader that decodes a mnemonic string read from an inner reader.
+///
+/// Words are tokenized incrementally, so only the current word and one decoded 4-byte chunk
+/// are held in memory besides the input buffer.  A run of letters longer than any word in the
+/// word list is rejected as an unrecognized word, and only its first few letters are kept for
+/// the error.  Decoding errors are returned as
+/// [`io::Error`]s of kind [`InvalidData`](io::ErrorKind::InvalidData) wrapping an
+/// [`Error`](crate::Error), which can be recovered by converting back with `From`.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Read;
+///
+/// let src = "digital-apollo-aroma--rival-artist-rebel";
+/// let mut reader = mnemonic::MnemonicReader::new(src.as_bytes());
+///
+/// let mut dest = Vec::new();
+/// reader.read_to_end(&mut dest).unwrap();
+/// assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+/// ```
+#[derive(Debug)]
+pub struct MnemonicReader<R, L = DefaultWordlist> {
+    inner: io::BufReader<R>,
+    wordlist: L,
+    /// The length of the longest word in `wordlist`.
+    max_word_len: usize,
+    /// The word currently being read, truncated to one byte longer than the longest valid word.
+    word: Vec<u8>,
+    /// The position of `word` in the input.
+    position: Position,
+    /// Number of input bytes consumed so far.
+    consumed: usize,
+    /// The 4-byte chunk currently being decoded.
+    x: u32,
+    /// Number of bytes decoded so far.
+    offset: usize,
+    /// Decoded bytes not yet returned to the caller.
+    out: [u8; 4],
+    out_pos: usize,
+    out_len: usize,
+    /// Set once the input is exhausted or a decoding error is returned.
+    done: bool,
+}
+
+impl<R: Read> MnemonicReader<R> {
+    /// Create a reader that decodes the mnemonic string read from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self::with_wordlist(inner, DefaultWordlist)
+    }
+}
+
+impl<R: Read, L: Wordlist> MnemonicReader<R, L> {
+    /// Create a reader that decodes the mnemonic string read from `inner`, using a custom word
+    /// list.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+    pub fn with_wordlist(inner: R, wordlist: L) -> Self {
+        mn_check_wordlist(&wordlist);
+        let max_word_len = (0..wordlist.base() + wordlist.remainder())
+            .map(|i| wordlist.word(i).len())
+            .max()
+            .unwrap_or(0);
+        MnemonicReader {
+            inner: io::BufReader::new(inner),
+            wordlist,
+            max_word_len,
+            word: Vec::new(),
+            position: Position::default(),
+            consumed: 0,
+            x: 0,
+            offset: 0,
+            out: [0; 4],
+            out_pos: 0,
+            out_len: 0,
+            done: false,
+        }
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Return the inner reader.
+    ///
+    /// Any input that was buffered but not yet decoded is lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Read the next word into `self.word`, which must be empty or hold a partial word from
+    /// an earlier call that failed.  Returns `false` at the end of input.
+    fn next_word(&mut self) -> io::Result<bool> {
+        loop {
+            let available = self.inner.fill_buf()?;
+            if available.is_empty() {
+                return Ok(!self.word.is_empty())
+            }
+            let mut used = 0;
+            let mut complete = false;
+            for &c in available {
+                if c.is_ascii_alphabetic() {
+                    if self.word.is_empty() {
+                        self.position.bytes = Some(self.consumed + used..self.consumed + used);
+                    }
+                    if self.word.len() <= self.max_word_len {
+                        self.word.push(c);
+                    }
+                    if let Some(ref mut bytes) = self.position.bytes {
+                        bytes.end += 1;
+                    }
+                } else if !self.word.is_empty() {
+                    complete = true;
+                }
+                used += 1;
+                if complete {
+                    break
+                }
+            }
+            self.inner.consume(used);
+            self.consumed += used;
+            if complete {
+                return Ok(true)
+            }
+        }
+    }
+
+    /// Decode words until a 4-byte chunk or the trailing bytes are ready in `self.out`.
+    fn fill_chunk(&mut self) -> Result<()> {
+        while self.next_word()? {
+            let i = self.wordlist.index(&self.word).ok_or_else(|| {
+                WordError::UnrecognizedWord.at(&self.word, self.position.clone())
+            })?;
+            mn_decode_word_index(i, self.wordlist.base(), &mut self.x, &mut self.offset)
+                .map_err(|e| e.at(&self.word, self.position.clone()))?;
+            self.word.clear();
+            self.position.word += 1;
+            if self.offset % 4 == 0 {
+                // Finished decoding this 4-byte chunk.
+                self.out = self.x.to_le_bytes();
+                self.out_pos = 0;
+                self.out_len = 4;
+                self.x = 0;
+                return Ok(())
+            }
+        }
+        self.done = true;
+        let remainder = self.offset % 4;
+        mn_decode_finish(self.x, remainder)?;
+        self.out = self.x.to_le_bytes();
+        self.out_pos = 0;
+        self.out_len = remainder;
+        Ok(())
+    }
+}
+
+impl<R: Read, L: Wordlist> Read for MnemonicReader<R, L> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.out_pos == self.out_len {
+            if self.done {
+                return Ok(0)
+            }
+            match self.fill_chunk() {
+                // I/O errors such as `Interrupted` may be retried, so leave the stream open.
+                Err(e @ Error::Io(_)) => return Err(e.into()),
+                Err(e) => {
+                    self.done = true;
+                    return Err(e.into())
+                }
+                Ok(()) => {}
+            }
+        }
+        let n = (self.out_len - self.out_pos).min(buf.len());
+        buf[..n].copy_from_slice(&self.out[self.out_pos..self.out_pos + n]);
+        self.out_pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+    use crate::to_string;
+    use crate::Error::*;
+
+    /// A reader that returns one byte per call, to exercise words split across reads.
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0)
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    /// A reader that fails with `Interrupted` before every other call.
+    struct Interrupting<R> {
+        inner: R,
+        interrupt: bool,
+    }
+
+    impl<R: Read> Read for Interrupting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into())
+            }
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_read_interrupted() {
+        let src = "digital-apollo-aroma--rival-artist-rebel";
+        let inner = Interrupting { inner: OneByte(src.as_bytes()), interrupt: false };
+        let mut dest = Vec::new();
+        MnemonicReader::new(inner).read_to_end(&mut dest).unwrap();
+        assert_eq!(dest, [101, 2, 240, 6, 108, 11, 20, 97]);
+    }
+
+    #[test]
+    fn test_read_with_wordlist() {
+        let wordlist = crate::wordlist::tests::Reversed;
+        let mut dest = Vec::new();
+        let src = OneByte(b"needle-russian-fax");
+        MnemonicReader::with_wordlist(src, wordlist).read_to_end(&mut dest).unwrap();
+        assert_eq!(dest, [0x01, 0xE2, 0x40]);
+    }
+
+    #[test]
+    fn test_read_24bit() {
+        let mut dest = Vec::new();
+        MnemonicReader::new(&b"consul-quiet-fax"[..]).read_to_end(&mut dest).unwrap();
+        assert_eq!(dest, [0x01, 0xE2, 0x40]);
+    }
+
+    #[test]
+    fn test_read_error() {
+        let mut dest = Vec::new();
+        let src = OneByte(b"digital apollo\n\naplolo");
+        let e = MnemonicReader::new(src).read_to_end(&mut dest).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "unrecognized word 'aplolo' at word 3 (bytes 16..22)");
+        assert!(matches!(Error::from(e), UnrecognizedWord { .. }));
+    }
+
+    #[test]
+    fn test_read_truncated() {
+        let mut dest = Vec::new();
+        let e = MnemonicReader::new(&b"rival-artist"[..]).read_to_end(&mut dest).unwrap_err();
+        assert!(matches!(Error::from(e), UnexpectedRemainder));
+    }
+
+    #[test]
+    fn test_read_long_word() {
+        let mut src = b"digital ".to_vec();
+        src.resize(100_000, b'a');
+        let mut dest = Vec::new();
+        let e = MnemonicReader::new(&src[..]).read_to_end(&mut dest).unwrap_err();
+        match Error::from(e) {
+            UnrecognizedWord { word, position } => {
+                assert_eq!(word, "aaaaaaaa");
+                assert_eq!(position.bytes, Some(8..100_000));
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    quickcheck! {
+        fn quickcheck_read_round_trip(src: Vec<u8>) -> bool {
+            let encoded = to_string(&src);
+            let mut decoded = Vec::new();
+            MnemonicReader::new(OneByte(encoded.as_bytes())).read_to_end(&mut decoded).unwrap();
+            decoded == src
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Serialize bytes and integers as mnemonic strings.
+//!
+//! This module is meant to be used with `#[serde(with = "mnemonic::serde")]` on a field of
+//! type `Vec<u8>`, `[u8; N]`, or an unsigned integer.  In human-readable formats such as JSON
+//! or TOML, the field is written as a string using [`to_string`](crate::to_string), and read
+//! using [`decode_to_vec`](crate::decode_to_vec).  In binary formats, it is written as raw
+//! bytes.  Integers are converted to and from little-endian bytes, so they are written the same
+//! way as by [`encode_u32`](crate::encode_u32) and the related functions.
+//!
+//! ## Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Config {
+//!     #[serde(with = "mnemonic::serde")]
+//!     key: [u8; 4],
+//!     #[serde(with = "mnemonic::serde")]
+//!     id: u32,
+//! }
+//!
+//! let config = Config { key: [101, 2, 240, 6], id: 0x01E240 };
+//! let json = serde_json::to_string(&config).unwrap();
+//! assert_eq!(json, r#"{"key":"digital-apollo-aroma","id":"quiz-block-academy"}"#);
+//! assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::Serializer;
+
+use crate::{decode_to_vec, to_string, Error};
+
+/// Types that can be serialized by this module.
+pub trait Bytes: Sized {
+    /// Call `f` with the bytes of `self`.
+    fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R;
+
+    /// Convert decoded bytes back into a value.
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error>;
+}
+
+impl Bytes for Vec<u8> {
+    fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Ok(bytes)
+    }
+}
+
+impl<const N: usize> Bytes for [u8; N] {
+    fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+        f(self)
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        let len = bytes.len();
+        bytes.try_into().map_err(|_| Error::WrongLength { expected: N, len })
+    }
+}
+
+macro_rules! impl_bytes_for_int {
+    ($($t:ty),*) => {$(
+        impl Bytes for $t {
+            fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                f(&self.to_le_bytes())
+            }
+
+            fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+                <[u8; core::mem::size_of::<$t>()]>::from_bytes(bytes).map(<$t>::from_le_bytes)
+            }
+        }
+    )*}
+}
+
+impl_bytes_for_int!(u8, u16, u32, u64, u128);
+
+/// Serialize `value` as a mnemonic string, or as bytes if the format is not human-readable.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where T: Bytes,
+          S: Serializer
+{
+    if serializer.is_human_readable() {
+        value.with_bytes(|bytes| serializer.serialize_str(&to_string(bytes)))
+    } else {
+        value.with_bytes(|bytes| serializer.serialize_bytes(bytes))
+    }
+}
+
+/// Deserialize a value from a mnemonic string, or from bytes if the format is not
+/// human-readable.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where T: Bytes,
+          D: Deserializer<'de>
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(MnemonicVisitor(PhantomData))
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+struct MnemonicVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Bytes> Visitor<'de> for MnemonicVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a mnemonic string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        decode_to_vec(v).and_then(T::from_bytes).map_err(E::custom)
+    }
+}
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Bytes> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        self.visit_byte_buf(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+        T::from_bytes(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        self.visit_byte_buf(bytes)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<T, E> {
+        self.visit_byte_buf(v.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Wrapper<T: Bytes>(#[serde(with = "crate::serde")] T);
+
+    #[test]
+    fn test_readable() {
+        let bytes = vec![101, 2, 240, 6, 108, 11, 20, 97];
+        assert_tokens(&Wrapper(bytes).readable(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("digital-apollo-aroma--rival-artist-rebel"),
+        ]);
+        assert_tokens(&Wrapper([101u8, 2]).readable(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("radio-academy"),
+        ]);
+        assert_tokens(&Wrapper(0x01E240u32).readable(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("quiz-block-academy"),
+        ]);
+
+        assert_de_tokens_error::<Readable<Wrapper<[u8; 3]>>>(&[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("radio-academy"),
+        ], "expected 3 bytes of decoded data, found 2");
+        assert_de_tokens_error::<Readable<Wrapper<u16>>>(&[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("radio-xyz"),
+        ], "unrecognized word 'xyz' at word 2 (bytes 6..9)");
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_tokens(&Wrapper(vec![101u8, 2, 240]).compact(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Bytes(&[101, 2, 240]),
+        ]);
+        assert_tokens(&Wrapper(0x0102u16).compact(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Bytes(&[2, 1]),
+        ]);
+        serde_test::assert_de_tokens(&Wrapper([1u8, 2]).compact(), &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ]);
+        assert_de_tokens_error::<Compact<Wrapper<u64>>>(&[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Bytes(&[1, 2]),
+        ], "expected 8 bytes of decoded data, found 2");
+    }
+}
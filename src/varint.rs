@@ -0,0 +1,125 @@
+use alloc::string::String;
+use core::fmt::Write;
+use core::str;
+
+use crate::error::WordError;
+use crate::{mn_encode_with, mn_resolve_exact, mn_words};
+use crate::{Error, Format, Position, Result, MN_BASE, MN_WORDS};
+
+/// The most words needed to encode a `u64`, since 1626^6 > 2^64.
+const MAX_WORDS: usize = 6;
+
+/// Encode `n` in as few words as possible.
+///
+/// Each word is one digit of `n` in base 1626, starting with the lowest digit, so numbers below
+/// 1626 take one word, numbers below 1626² take two, and so on up to six words for the largest
+/// `u64`.  The words are separated as in the default [`Format`].
+///
+/// Unlike [`encode_u64`](crate::encode_u64), the result cannot be read by
+/// [`decode`](crate::decode); use [`decode_varint`] instead.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(mnemonic::encode_varint(5), "admiral");
+/// assert_eq!(mnemonic::encode_varint(1626), "academy-acrobat");
+/// assert_eq!(mnemonic::decode_varint("academy-acrobat").unwrap(), 1626);
+/// ```
+pub fn encode_varint(n: u64) -> String {
+    let mut digits = [0; MAX_WORDS];
+    let mut len = 0;
+    let mut rest = n;
+    loop {
+        digits[len] = (rest % MN_BASE as u64) as u32;
+        rest /= MN_BASE as u64;
+        len += 1;
+        if rest == 0 {
+            break
+        }
+    }
+
+    let mut s = String::new();
+    mn_encode_with(&[], &Format::default(), MN_BASE, digits[..len].iter().copied(),
+                   |index| MN_WORDS[index as usize],
+                   |piece| s.write_str(str::from_utf8(piece).unwrap())).unwrap();
+    s
+}
+
+/// Decode a number written by [`encode_varint`].
+///
+/// Any non-letters are accepted between words, as in [`decode`](crate::decode).  Fails with
+/// [`Error::NonCanonical`] if the last word is an unneeded zero digit, so that each number has
+/// only one encoding, or [`Error::InvalidEncoding`] if the number does not fit in a `u64`.
+pub fn decode_varint<S: AsRef<[u8]>>(src: S) -> Result<u64> {
+    let mut n = 0u64;
+    let mut scale = Some(1u64);
+    let mut last = None;
+    for (i, (start, word)) in mn_words(src.as_ref()).enumerate() {
+        let position = Position { word: i, bytes: Some(start..start + word.len()) };
+        let digit = match mn_resolve_exact(word, &position)? {
+            0 => Some(0),
+            digit if digit < MN_BASE => scale.and_then(|scale| scale.checked_mul(digit as u64)),
+            _ => None,
+        };
+        n = digit.and_then(|digit| n.checked_add(digit))
+                 .ok_or_else(|| WordError::InvalidEncoding.at(word, position.clone()))?;
+        scale = scale.and_then(|scale| scale.checked_mul(MN_BASE as u64));
+        last = Some((digit == Some(0), word, position));
+    }
+    match last {
+        None => Err(Error::UnexpectedRemainder),
+        Some((true, word, position)) if position.word > 0 => {
+            Err(WordError::NonCanonical.at(word, position))
+        }
+        Some(_) => Ok(n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_encode_varint() {
+        assert_eq!(encode_varint(0), "academy");
+        assert_eq!(encode_varint(1625), crate::word_for_index(1625).unwrap());
+        assert_eq!(encode_varint(1626 * 1626), "academy-academy-acrobat");
+        assert_eq!(encode_varint(1626 * 1626 * 1626), "academy-academy-academy--acrobat");
+        assert_eq!(encode_varint(u64::MAX).split('-').filter(|w| !w.is_empty()).count(), MAX_WORDS);
+    }
+
+    #[test]
+    fn test_decode_varint() {
+        assert_eq!(decode_varint("academy").unwrap(), 0);
+        assert_eq!(decode_varint(" academy acrobat\n").unwrap(), 1626);
+
+        let e = decode_varint("admiral-academy").unwrap_err();
+        assert_eq!(e.to_string(),
+                   "non-canonical encoding: unneeded word 'academy' at word 2 (bytes 8..15)");
+        assert!(matches!(decode_varint("").unwrap_err(), Error::UnexpectedRemainder));
+        let e = decode_varint("admiral xyz").unwrap_err();
+        assert!(matches!(e, Error::UnrecognizedWord { .. }));
+
+        let max = encode_varint(u64::MAX);
+        let e = decode_varint(max.replace("--", "-academy--")).unwrap_err();
+        assert!(matches!(e, Error::InvalidEncoding { .. }));
+        let e = decode_varint(max + "-acrobat").unwrap_err();
+        assert!(matches!(e, Error::InvalidEncoding { position: Position { word: 6, .. }, .. }));
+
+        let e = decode_varint(crate::word_for_index(MN_BASE as usize).unwrap()).unwrap_err();
+        assert!(matches!(e, Error::InvalidEncoding { .. }));
+    }
+
+    quickcheck! {
+        fn quickcheck_varint(n: u64) -> bool {
+            decode_varint(encode_varint(n)).unwrap() == n
+        }
+
+        fn quickcheck_varint_len(n: u64) -> bool {
+            let words = encode_varint(n).split('-').filter(|w| !w.is_empty()).count();
+            words == 1 || n >= 1626u64.pow(words as u32 - 1)
+        }
+    }
+}
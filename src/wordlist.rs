@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use crate::{mn_word_index, MN_BASE, MN_REMAINDER, MN_WORDS, MN_WORDS_SORTED};
+
+/// A list of words to encode with.
+///
+/// Data is encoded the same way with any word list: each 32-bit chunk is split into three
+/// base-[`base`](Wordlist::base) digits, and each digit is written as the word with that index.
+/// A trailing 24-bit chunk uses one of the [`remainder`](Wordlist::remainder) extra words,
+/// which follow the base words, for its third digit.
+///
+/// The base must be at least 1626 (so that three digits can hold 32 bits) and less than 65536,
+/// and there must be enough remainder words to hold the third digit of any 24-bit value.  No
+/// word may appear twice.
+///
+/// A word list can be used with [`Encoder::wordlist`](crate::Encoder::wordlist) and
+/// [`Decoder::wordlist`](crate::Decoder::wordlist), and so with any format and decoding option,
+/// or with [`MnemonicWriter::with_wordlist`] and [`MnemonicReader::with_wordlist`].
+///
+/// ## Localized lists
+///
+/// Input is split into words at every byte that is not an ASCII letter, so words must be
+/// spelled with ASCII letters only.  A list in a language with other letters must use an ASCII
+/// spelling of each word, such as "uber" or "ueber" for "über".  With the `unicode` feature, a
+/// [`Decoder`](crate::Decoder) that [normalizes](crate::Decoder::normalize) its input removes
+/// accents before looking words up, so input typed as "über" is read as "uber".
+///
+/// [`MnemonicWriter::with_wordlist`]: crate::MnemonicWriter::with_wordlist
+/// [`MnemonicReader::with_wordlist`]: crate::MnemonicReader::with_wordlist
+///
+/// ## Example
+///
+/// A word list with the standard words in reverse order:
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use mnemonic::Wordlist;
+///
+/// struct Reversed;
+///
+/// impl Wordlist for Reversed {
+///     fn base(&self) -> u32 {
+///         1626
+///     }
+///
+///     fn remainder(&self) -> u32 {
+///         7
+///     }
+///
+///     fn word(&self, index: u32) -> &[u8] {
+///         match index {
+///             0..=1625 => mnemonic::MN_WORDS[1625 - index as usize],
+///             _ => mnemonic::MN_WORDS[index as usize],
+///         }
+///     }
+/// }
+///
+/// let mut dest = Vec::<u8>::new();
+/// mnemonic::encode_with_wordlist([0x01, 0xE2, 0x40], mnemonic::MN_FDEFAULT, &Reversed, &mut dest)
+///     .unwrap();
+/// assert_eq!(dest, b"needle-russian-fax");
+///
+/// let mut decoded = Vec::<u8>::new();
+/// mnemonic::decode_with_wordlist("needle-russian-fax", &Reversed, &mut decoded).unwrap();
+/// assert_eq!(decoded, [0x01, 0xE2, 0x40]);
+///
+/// let encoder = mnemonic::Encoder::new().wordlist(Reversed).separator(" ").unwrap();
+/// assert_eq!(encoder.encode_to_string([0x01, 0xE2, 0x40]), "needle russian fax");
+///
+/// let decoder = mnemonic::Decoder::new().wordlist(Reversed).prefixes(true);
+/// assert_eq!(decoder.decode_to_vec("need russ fax").unwrap(), [0x01, 0xE2, 0x40]);
+/// # }
+/// ```
+pub trait Wordlist {
+    /// The number of words used for ordinary digits.
+    fn base(&self) -> u32;
+
+    /// The number of extra words used only for the third digit of a 24-bit remainder.
+    fn remainder(&self) -> u32;
+
+    /// The word with the given index, which is less than `base() + remainder()`.
+    fn word(&self, index: u32) -> &[u8];
+
+    /// The index of `word`, if it is in the list.
+    ///
+    /// The default implementation searches the whole list.
+    fn index(&self, word: &[u8]) -> Option<u32> {
+        (0..self.base() + self.remainder()).find(|&i| self.word(i) == word)
+    }
+
+    /// The indices of all words that start with `prefix`, in any order.
+    ///
+    /// The default implementation searches the whole list.
+    fn words_with_prefix(&self, prefix: &[u8]) -> Vec<u32> {
+        (0..self.base() + self.remainder()).filter(|&i| self.word(i).starts_with(prefix)).collect()
+    }
+}
+
+impl<L: Wordlist + ?Sized> Wordlist for &L {
+    fn base(&self) -> u32 {
+        (**self).base()
+    }
+
+    fn remainder(&self) -> u32 {
+        (**self).remainder()
+    }
+
+    fn word(&self, index: u32) -> &[u8] {
+        (**self).word(index)
+    }
+
+    fn index(&self, word: &[u8]) -> Option<u32> {
+        (**self).index(word)
+    }
+
+    fn words_with_prefix(&self, prefix: &[u8]) -> Vec<u32> {
+        (**self).words_with_prefix(prefix)
+    }
+}
+
+/// The standard word list, [`MN_WORDS`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultWordlist;
+
+impl Wordlist for DefaultWordlist {
+    fn base(&self) -> u32 {
+        MN_BASE
+    }
+
+    fn remainder(&self) -> u32 {
+        MN_REMAINDER as u32
+    }
+
+    fn word(&self, index: u32) -> &[u8] {
+        MN_WORDS[index as usize]
+    }
+
+    fn index(&self, word: &[u8]) -> Option<u32> {
+        mn_word_index(word)
+    }
+
+    fn words_with_prefix(&self, prefix: &[u8]) -> Vec<u32> {
+        // Words sharing a prefix are adjacent in sorted order.
+        let start = MN_WORDS_SORTED.partition_point(|&i| MN_WORDS[i as usize] < prefix);
+        MN_WORDS_SORTED[start..].iter()
+                                .take_while(|&&i| MN_WORDS[i as usize].starts_with(prefix))
+                                .map(|&i| i as u32)
+                                .collect()
+    }
+}
+
+/// Panic unless `wordlist` has a valid base and enough remainder words.
+pub(crate) fn mn_check_wordlist<L: Wordlist + ?Sized>(wordlist: &L) {
+    let base = wordlist.base();
+    assert!((MN_BASE..1 << 16).contains(&base), "word list base must be between 1626 and 65535");
+    let square = base as u64 * base as u64;
+    let needed = ((1 << 24) + square - 1) / square;
+    assert!(wordlist.remainder() as u64 >= needed, "word list needs at least {} remainder words", needed);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+
+    /// The standard words with the ordinary words in reverse order.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub(crate) struct Reversed;
+
+    impl Wordlist for Reversed {
+        fn base(&self) -> u32 { MN_BASE }
+        fn remainder(&self) -> u32 { MN_REMAINDER as u32 }
+        fn word(&self, index: u32) -> &[u8] {
+            match index {
+                0..=1625 => MN_WORDS[1625 - index as usize],
+                _ => MN_WORDS[index as usize],
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_wordlist() {
+        mn_check_wordlist(&DefaultWordlist);
+        for i in 0..DefaultWordlist.base() + DefaultWordlist.remainder() {
+            let word = DefaultWordlist.word(i);
+            assert_eq!(DefaultWordlist.index(word), Some(i));
+            assert_eq!(Wordlist::index(&&DefaultWordlist, word), Some(i));
+        }
+        assert_eq!(Reversed.index(b"academy"), Some(1625));
+        assert_eq!(Reversed.index(b"ego"), Some(1626));
+        assert_eq!(Reversed.index(b"aplolo"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "remainder words")]
+    fn test_check_wordlist() {
+        struct Short;
+        impl Wordlist for Short {
+            fn base(&self) -> u32 { 1626 }
+            fn remainder(&self) -> u32 { 6 }
+            fn word(&self, index: u32) -> &[u8] { MN_WORDS[index as usize] }
+        }
+        mn_check_wordlist(&Short);
+    }
+
+    quickcheck! {
+        #[cfg(feature = "std")]
+        fn quickcheck_wordlist_round_trip(src: Vec<u8>) -> bool {
+            let mut encoded = Vec::new();
+            crate::encode_with_wordlist(&src, "x-x-x--", &Reversed, &mut encoded).unwrap();
+            let mut decoded = Vec::new();
+            crate::decode_with_wordlist(&encoded, &Reversed, &mut decoded).unwrap();
+            decoded == src
+        }
+    }
+}
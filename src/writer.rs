@@ -0,0 +1,161 @@
+use std::io;
+use std::io::prelude::*;
+
+use crate::wordlist::mn_check_wordlist;
+use crate::{mn_encode_word_index, mn_words_required, DefaultWordlist, Format, Wordlist};
+
+/// A writer that mnemonic-encodes all bytes written to it.
+///
+/// At most one 4-byte chunk of input is buffered at a time.  Words are written to the inner
+/// writer as soon as each chunk is complete, so data of any size can be encoded in constant
+/// memory.
+///
+/// Any trailing partial chunk is only encoded when [`finish`](MnemonicWriter::finish) is
+/// called.  Dropping the writer without calling `finish` discards those bytes.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = mnemonic::MnemonicWriter::new(Vec::new());
+/// writer.write_all(&[101, 2, 240, 6, 108]).unwrap();
+/// writer.write_all(&[11, 20, 97]).unwrap();
+/// let dest = writer.finish().unwrap();
+///
+/// assert_eq!(dest, &b"digital-apollo-aroma--rival-artist-rebel"[..]);
+/// ```
+#[derive(Debug)]
+pub struct MnemonicWriter<W: Write, L = DefaultWordlist> {
+    inner: W,
+    format: Format,
+    wordlist: L,
+    /// Number of words written so far.
+    words: usize,
+    /// The current partial chunk.
+    buf: [u8; 4],
+    /// Number of bytes in `buf`.
+    len: usize,
+}
+
+impl<W: Write> MnemonicWriter<W> {
+    /// Create a writer that encodes to `inner` using the default format.
+    pub fn new(inner: W) -> Self {
+        Self::with_format(inner, Format::default())
+    }
+
+    /// Create a writer that encodes to `inner` using a custom format.
+    pub fn with_format(inner: W, format: Format) -> Self {
+        Self::with_wordlist(inner, format, DefaultWordlist)
+    }
+}
+
+impl<W: Write, L: Wordlist> MnemonicWriter<W, L> {
+    /// Create a writer that encodes to `inner` using a custom format and word list.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `wordlist` does not meet the requirements described in [`Wordlist`].
+    pub fn with_wordlist(inner: W, format: Format, wordlist: L) -> Self {
+        mn_check_wordlist(&wordlist);
+        MnemonicWriter { inner, format, wordlist, words: 0, buf: [0; 4], len: 0 }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner writer.
+    ///
+    /// Writing directly to the inner writer may corrupt the encoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Encode any buffered bytes, and return the inner writer.
+    ///
+    /// A trailing 3-byte chunk is encoded using one of the 24-bit remainder words.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_chunk()?;
+        self.format.finish(self.words, &mut |s| self.inner.write_all(s))?;
+        Ok(self.inner)
+    }
+
+    /// Write the words for the buffered chunk, and clear the buffer.
+    fn write_chunk(&mut self) -> io::Result<()> {
+        let chunk = &self.buf[..self.len];
+        for n in 0..mn_words_required(chunk) {
+            let word = self.wordlist.word(mn_encode_word_index(chunk, n, self.wordlist.base()));
+            self.format.write_word(self.words, word, &mut |s| self.inner.write_all(s))?;
+            self.words += 1;
+        }
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write, L: Wordlist> Write for MnemonicWriter<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            let n = (4 - self.len).min(buf.len() - written);
+            self.buf[self.len..self.len + n].copy_from_slice(&buf[written..written + n]);
+            self.len += n;
+            written += n;
+            if self.len == 4 {
+                self.write_chunk()?;
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+    use super::*;
+    use crate::{encode_with_format, to_string};
+
+    #[test]
+    fn test_write_24bit() {
+        let mut writer = MnemonicWriter::new(Vec::new());
+        writer.write_all(&[0x01, 0xE2, 0x40]).unwrap();
+        assert_eq!(writer.finish().unwrap(), b"consul-quiet-fax");
+    }
+
+    #[test]
+    fn test_write_with_format() {
+        let src = [101, 2, 240, 6, 108, 11, 20, 97];
+        let mut expected = Vec::new();
+        encode_with_format(src, "x x\n", &mut expected).unwrap();
+
+        let mut writer = MnemonicWriter::with_format(Vec::new(), Format::new("x x\n").unwrap());
+        for b in src {
+            writer.write_all(&[b]).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_with_wordlist() {
+        let wordlist = crate::wordlist::tests::Reversed;
+        let mut writer = MnemonicWriter::with_wordlist(Vec::new(), Format::default(), wordlist);
+        writer.write_all(&[0x01, 0xE2, 0x40]).unwrap();
+        assert_eq!(writer.finish().unwrap(), b"needle-russian-fax");
+    }
+
+    quickcheck! {
+        fn quickcheck_split_writes(src: Vec<u8>, split: usize) -> bool {
+            let split = if src.is_empty() { 0 } else { split % src.len() };
+            let mut writer = MnemonicWriter::new(Vec::new());
+            writer.write_all(&src[..split]).unwrap();
+            writer.write_all(&src[split..]).unwrap();
+            writer.finish().unwrap() == to_string(&src).into_bytes()
+        }
+    }
+}